@@ -548,7 +548,9 @@ fn main() {
         .include("physx/physx/source/lowlevel/common/include/utils")
         .include("physx/physx/source/geomutils/src/contact")
         .include("physx/physx/source/geomutils/src/pcm")
+        .include("physx/physx/source/geomutils/src/mesh")
         .include("physx/physx/source/simulationcontroller/src")
+        .include("physx/physx/source/physxcharacterkinematic/src")
         .include("physx/physx/source/lowlevelaabb/include");
 
     if cfg!(feature = "profile") {
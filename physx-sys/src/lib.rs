@@ -200,6 +200,8 @@ pub type WakeSleepCallback = unsafe extern "C" fn(*mut c_void, *const *const PxA
 pub type AdvanceCallback =
     unsafe extern "C" fn(*mut c_void, *const *const PxRigidBody, *const PxTransform, u32);
 
+pub type ContactModifyCallback = unsafe extern "C" fn(*mut c_void, *mut PxContactModifyPair, u32);
+
 // Function pointers in Rust are normally not nullable (which is why they don't require unsafe to call)
 // but we need them to be, so we simply wrap them in Option<>. An Option<funcptr> is luckily represented
 // by the compiler as a simple pointer with null representing None, so this is compatible with the C struct.
@@ -250,6 +252,83 @@ pub type RaycastHitCallback = unsafe extern "C" fn(
     *const c_void,
 ) -> PxQueryHitType;
 
+pub type QueryPreFilterCallback = unsafe extern "C" fn(
+    *const PxFilterData,
+    *const PxShape,
+    *const PxRigidActor,
+    *mut PxHitFlags,
+    *mut c_void,
+) -> PxQueryHitType;
+
+/// The hit is a `PxRaycastHit`, `PxSweepHit` or `PxOverlapHit` depending on the query.
+pub type QueryPostFilterCallback = unsafe extern "C" fn(
+    *const PxFilterData,
+    *const PxQueryHit,
+    *const PxShape,
+    *const PxRigidActor,
+    *mut c_void,
+) -> PxQueryHitType;
+
+#[repr(C)]
+pub struct ConstraintVisualizerCallbacks {
+    pub visualize_joint_frames:
+        unsafe extern "C" fn(*mut c_void, parent: *const PxTransform, child: *const PxTransform),
+    pub visualize_linear_limit: unsafe extern "C" fn(
+        *mut c_void,
+        t0: *const PxTransform,
+        t1: *const PxTransform,
+        value: f32,
+        active: bool,
+    ),
+    pub visualize_angular_limit: unsafe extern "C" fn(
+        *mut c_void,
+        t0: *const PxTransform,
+        lower: f32,
+        upper: f32,
+        active: bool,
+    ),
+    pub visualize_limit_cone: unsafe extern "C" fn(
+        *mut c_void,
+        t: *const PxTransform,
+        tan_q_swing_y: f32,
+        tan_q_swing_z: f32,
+        active: bool,
+    ),
+    pub visualize_double_cone:
+        unsafe extern "C" fn(*mut c_void, t: *const PxTransform, angle: f32, active: bool),
+    pub visualize_line:
+        unsafe extern "C" fn(*mut c_void, p0: *const PxVec3, p1: *const PxVec3, color: u32),
+    pub user_data: *mut c_void,
+}
+
+#[repr(C)]
+pub struct ControllerHitReportCallbacks {
+    pub on_shape_hit: unsafe extern "C" fn(*mut c_void, hit: *const PxControllerShapeHit),
+    pub on_controller_hit: unsafe extern "C" fn(*mut c_void, hit: *const PxControllersHit),
+    pub on_obstacle_hit: unsafe extern "C" fn(*mut c_void, hit: *const PxControllerObstacleHit),
+    /// Called with `user_data` when the report is destroyed.
+    pub drop_user_data: unsafe extern "C" fn(*mut c_void),
+    pub user_data: *mut c_void,
+}
+
+#[repr(C)]
+pub struct ControllerBehaviorCallbacks {
+    pub get_shape_behavior_flags: unsafe extern "C" fn(
+        *mut c_void,
+        shape: *const PxShape,
+        actor: *const PxActor,
+    ) -> PxControllerBehaviorFlags,
+    pub get_controller_behavior_flags: unsafe extern "C" fn(
+        *mut c_void,
+        controller: *const PxController,
+    ) -> PxControllerBehaviorFlags,
+    pub get_obstacle_behavior_flags:
+        unsafe extern "C" fn(*mut c_void, obstacle: *const PxObstacle) -> PxControllerBehaviorFlags,
+    /// Called with `user_data` when the callback is destroyed.
+    pub drop_user_data: unsafe extern "C" fn(*mut c_void),
+    pub user_data: *mut c_void,
+}
+
 #[repr(C)]
 pub struct FilterShaderCallbackInfo {
     pub attributes0: u32,
@@ -319,6 +398,31 @@ extern "C" {
 
     pub fn PxParticleBufferDesc_setPositions(_address: *mut PxParticleBufferDesc, value: *mut PxVec4);
 
+    pub fn PxTriangleMeshDesc_setMaterialIndices(_address: *mut PxTriangleMeshDesc, value: *const u16, stride: u32);
+
+    pub fn PxTriangleMeshDesc_getMaterialIndices(_address: *const PxTriangleMeshDesc, stride: *mut u32) -> *const u16;
+
+    /// `mesh_cooking_hint` is a `PxMeshCookingHint`, 0 for simulation and 1 for cooking performance.
+    pub fn PxCookingParams_setMidphaseBVH33(_address: *mut PxCookingParams, mesh_cooking_hint: u32, mesh_size_performance_trade_off: f32);
+
+    /// Only valid if the midphase type is `PxMeshMidPhase::Bvh33`.
+    pub fn PxCookingParams_getMidphaseBVH33(_address: *const PxCookingParams, mesh_cooking_hint: *mut u32, mesh_size_performance_trade_off: *mut f32);
+
+    pub fn PxCookingParams_setMidphaseBVH34(_address: *mut PxCookingParams, desc: *const PxBVH34MidphaseDesc);
+
+    /// Only valid if the midphase type is `PxMeshMidPhase::Bvh34`.
+    pub fn PxCookingParams_getMidphaseBVH34(_address: *const PxCookingParams, desc: *mut PxBVH34MidphaseDesc);
+
+    /// Returns false for BVH34 meshes with a quantized tree, which `PxTriangleMesh_refitBVH_mut`
+    /// can not refit.
+    pub fn PxTriangleMesh_canRefitBVH(mesh: *const PxTriangleMesh) -> bool;
+
+    /// The `reportCallback` of the descriptor the controller was created from.
+    pub fn PxController_getReportCallback(controller: *const PxController) -> *mut PxUserControllerHitReport;
+
+    /// The `behaviorCallback` of the descriptor the controller was created from.
+    pub fn PxController_getBehaviorCallback(controller: *const PxController) -> *mut PxControllerBehaviorCallback;
+
     pub fn alloc_pinned_host_buffer_pxu32(
         cuda_context_manager: *mut PxCudaContextManager,
         num_elements: u32,
@@ -355,6 +459,28 @@ extern "C" {
         userdata: *mut c_void,
     ) -> *mut PxQueryFilterCallback;
 
+    /// Destroy the returned callback object using PxQueryFilterCallback_delete.
+    pub fn create_query_filter_callback(
+        pre_filter: QueryPreFilterCallback,
+        post_filter: QueryPostFilterCallback,
+        userdata: *mut c_void,
+    ) -> *mut PxQueryFilterCallback;
+
+    /// Destroy the returned visualizer using destroy_constraint_visualizer.
+    pub fn create_constraint_visualizer(
+        callbacks: *const ConstraintVisualizerCallbacks,
+    ) -> *mut PxConstraintVisualizer;
+
+    pub fn destroy_constraint_visualizer(visualizer: *mut PxConstraintVisualizer);
+
+    /// Emit the frames and limits of the joint selected by `flags` (`PxConstraintVisualizationFlag`)
+    /// into the visualizer.  Returns false if the joint has no visualization function.
+    pub fn PxJoint_visualize(
+        joint: *const PxJoint,
+        visualizer: *mut PxConstraintVisualizer,
+        flags: u32,
+    ) -> bool;
+
     pub fn create_raycast_buffer() -> *mut PxRaycastCallback;
     pub fn create_sweep_buffer() -> *mut PxSweepCallback;
     pub fn create_overlap_buffer() -> *mut PxOverlapCallback;
@@ -433,6 +559,51 @@ extern "C" {
 
     pub fn destroy_simulation_event_callbacks(callback: *mut PxSimulationEventCallback);
 
+    /// The returned callback implements both PxContactModifyCallback and PxCCDContactModifyCallback,
+    /// use get_ccd_contact_modify_callback to get the latter.  Destroy it using
+    /// destroy_contact_modify_callback.
+    pub fn create_contact_modify_callback(
+        callback: ContactModifyCallback,
+        ccd_callback: ContactModifyCallback,
+        userdata: *mut c_void,
+    ) -> *mut PxContactModifyCallback;
+
+    pub fn get_ccd_contact_modify_callback(
+        callback: *mut PxContactModifyCallback,
+    ) -> *mut PxCCDContactModifyCallback;
+
+    /// Returns null if the callback was not created by create_contact_modify_callback.
+    pub fn get_contact_modify_user_data(callback: *mut PxContactModifyCallback) -> *mut c_void;
+
+    pub fn destroy_contact_modify_callback(callback: *mut PxContactModifyCallback);
+
+    /// Destroy the returned report using destroy_controller_hit_report.
+    pub fn create_controller_hit_report(
+        callbacks: *const ControllerHitReportCallbacks,
+    ) -> *mut PxUserControllerHitReport;
+
+    /// Drops the user data of the report and deletes it.  Does nothing for reports not
+    /// created by create_controller_hit_report.
+    pub fn destroy_controller_hit_report(report: *mut PxUserControllerHitReport);
+
+    /// Destroy the returned callback using destroy_controller_behavior_callback.
+    pub fn create_controller_behavior_callback(
+        callbacks: *const ControllerBehaviorCallbacks,
+    ) -> *mut PxControllerBehaviorCallback;
+
+    /// Drops the user data of the callback and deletes it.  Does nothing for callbacks not
+    /// created by create_controller_behavior_callback.
+    pub fn destroy_controller_behavior_callback(callback: *mut PxControllerBehaviorCallback);
+
+    /// Same as PxSerialization_createCollectionFromXml, but cooks the meshes in the collection
+    /// with `params` instead of taking a PxCooking.
+    pub fn create_collection_from_xml(
+        input_data: *mut PxInputData,
+        params: *const PxCookingParams,
+        sr: *mut PxSerializationRegistry,
+        external_refs: *const PxCollection,
+    ) -> *mut PxCollection;
+
     /// Override the default filter shader in the scene with a custom function.
     /// If call_default_filter_shader_first is set to true, this will first call the
     /// built-in PhysX filter (that matches Physx 2.8 behavior) before your callback.
@@ -1,5 +1,9 @@
 #include "PxPhysicsAPI.h"
 #include "NpParticleSystem.h"
+#include "NpConstraint.h"
+#include "GuTriangleMeshBV4.h"
+#include "CctBoxController.h"
+#include "CctCapsuleController.h"
 #include <cstdint>
 #include "iostream"
 #include "physx_generated.hpp"
@@ -178,6 +182,180 @@ public:
     }
 };
 
+typedef void (*ContactModifyCallback)(void *userData, PxContactModifyPair *pairs, PxU32 count);
+
+class ContactModifyTrampoline : public PxContactModifyCallback, public PxCCDContactModifyCallback {
+public:
+    ContactModifyTrampoline(ContactModifyCallback callback, ContactModifyCallback ccdCallback, void *userData)
+            : mCallback(callback), mCCDCallback(ccdCallback), mUserData(userData) {}
+
+    void onContactModify(PxContactModifyPair *const pairs, PxU32 count) override {
+        mCallback(mUserData, pairs, count);
+    }
+
+    void onCCDContactModify(PxContactModifyPair *const pairs, PxU32 count) override {
+        mCCDCallback(mUserData, pairs, count);
+    }
+
+    ContactModifyCallback mCallback;
+    ContactModifyCallback mCCDCallback;
+    void *mUserData;
+};
+
+typedef PxQueryHitType::Enum (*QueryPreFilterCallback)(const PxFilterData *filterData, const PxShape *shape,
+                                                        const PxRigidActor *actor, PxHitFlags *hitFlags,
+                                                        void *userData);
+
+typedef PxQueryHitType::Enum (*QueryPostFilterCallback)(const PxFilterData *filterData, const PxQueryHit *hit,
+                                                         const PxShape *shape, const PxRigidActor *actor,
+                                                         void *userData);
+
+class QueryFilterTrampoline : public PxQueryFilterCallback {
+public:
+    QueryFilterTrampoline(QueryPreFilterCallback preFilter, QueryPostFilterCallback postFilter, void *userData)
+            : mPreFilter(preFilter), mPostFilter(postFilter), mUserData(userData) {}
+
+    QueryPreFilterCallback mPreFilter;
+    QueryPostFilterCallback mPostFilter;
+    void *mUserData;
+
+    virtual PxQueryHitType::Enum
+    preFilter(const PxFilterData &filterData, const PxShape *shape, const PxRigidActor *actor, PxHitFlags &hitFlags) {
+        return mPreFilter(&filterData, shape, actor, &hitFlags, mUserData);
+    }
+
+    virtual PxQueryHitType::Enum
+    postFilter(const PxFilterData &filterData, const PxQueryHit &hit, const PxShape *shape, const PxRigidActor *actor) {
+        return mPostFilter(&filterData, &hit, shape, actor, mUserData);
+    }
+};
+
+struct ConstraintVisualizerCallbacks {
+    void (*visualizeJointFrames)(void *userData, const PxTransform *parent, const PxTransform *child);
+    void (*visualizeLinearLimit)(void *userData, const PxTransform *t0, const PxTransform *t1, PxReal value,
+                                 bool active);
+    void (*visualizeAngularLimit)(void *userData, const PxTransform *t0, PxReal lower, PxReal upper, bool active);
+    void (*visualizeLimitCone)(void *userData, const PxTransform *t, PxReal tanQSwingY, PxReal tanQSwingZ,
+                               bool active);
+    void (*visualizeDoubleCone)(void *userData, const PxTransform *t, PxReal angle, bool active);
+    void (*visualizeLine)(void *userData, const PxVec3 *p0, const PxVec3 *p1, PxU32 color);
+    void *userData;
+};
+
+class ConstraintVisualizerTrampoline : public PxConstraintVisualizer {
+public:
+    ConstraintVisualizerTrampoline(const ConstraintVisualizerCallbacks *callbacks) : mCallbacks(*callbacks) {}
+
+    void visualizeJointFrames(const PxTransform &parent, const PxTransform &child) override {
+        mCallbacks.visualizeJointFrames(mCallbacks.userData, &parent, &child);
+    }
+
+    void visualizeLinearLimit(const PxTransform &t0, const PxTransform &t1, PxReal value, bool active) override {
+        mCallbacks.visualizeLinearLimit(mCallbacks.userData, &t0, &t1, value, active);
+    }
+
+    void visualizeAngularLimit(const PxTransform &t0, PxReal lower, PxReal upper, bool active) override {
+        mCallbacks.visualizeAngularLimit(mCallbacks.userData, &t0, lower, upper, active);
+    }
+
+    void visualizeLimitCone(const PxTransform &t, PxReal tanQSwingY, PxReal tanQSwingZ, bool active) override {
+        mCallbacks.visualizeLimitCone(mCallbacks.userData, &t, tanQSwingY, tanQSwingZ, active);
+    }
+
+    void visualizeDoubleCone(const PxTransform &t, PxReal angle, bool active) override {
+        mCallbacks.visualizeDoubleCone(mCallbacks.userData, &t, angle, active);
+    }
+
+    void visualizeLine(const PxVec3 &p0, const PxVec3 &p1, PxU32 color) override {
+        mCallbacks.visualizeLine(mCallbacks.userData, &p0, &p1, color);
+    }
+
+    ConstraintVisualizerCallbacks mCallbacks;
+};
+
+struct ControllerHitReportCallbacks {
+    void (*onShapeHit)(void *userData, const PxControllerShapeHit *hit);
+    void (*onControllerHit)(void *userData, const PxControllersHit *hit);
+    void (*onObstacleHit)(void *userData, const PxControllerObstacleHit *hit);
+    void (*dropUserData)(void *userData);
+    void *userData;
+};
+
+class ControllerHitReportTrampoline : public PxUserControllerHitReport {
+public:
+    ControllerHitReportTrampoline(const ControllerHitReportCallbacks *callbacks) : mCallbacks(*callbacks) {}
+
+    ~ControllerHitReportTrampoline() override {
+        mCallbacks.dropUserData(mCallbacks.userData);
+    }
+
+    void onShapeHit(const PxControllerShapeHit &hit) override {
+        mCallbacks.onShapeHit(mCallbacks.userData, &hit);
+    }
+
+    void onControllerHit(const PxControllersHit &hit) override {
+        mCallbacks.onControllerHit(mCallbacks.userData, &hit);
+    }
+
+    void onObstacleHit(const PxControllerObstacleHit &hit) override {
+        mCallbacks.onObstacleHit(mCallbacks.userData, &hit);
+    }
+
+    ControllerHitReportCallbacks mCallbacks;
+};
+
+struct ControllerBehaviorCallbacks {
+    PxU8 (*getShapeBehaviorFlags)(void *userData, const PxShape *shape, const PxActor *actor);
+    PxU8 (*getControllerBehaviorFlags)(void *userData, const PxController *controller);
+    PxU8 (*getObstacleBehaviorFlags)(void *userData, const PxObstacle *obstacle);
+    void (*dropUserData)(void *userData);
+    void *userData;
+};
+
+class ControllerBehaviorTrampoline : public PxControllerBehaviorCallback {
+public:
+    ControllerBehaviorTrampoline(const ControllerBehaviorCallbacks *callbacks) : mCallbacks(*callbacks) {}
+
+    ~ControllerBehaviorTrampoline() override {
+        mCallbacks.dropUserData(mCallbacks.userData);
+    }
+
+    PxControllerBehaviorFlags getBehaviorFlags(const PxShape &shape, const PxActor &actor) override {
+        return PxControllerBehaviorFlags(mCallbacks.getShapeBehaviorFlags(mCallbacks.userData, &shape, &actor));
+    }
+
+    PxControllerBehaviorFlags getBehaviorFlags(const PxController &controller) override {
+        return PxControllerBehaviorFlags(mCallbacks.getControllerBehaviorFlags(mCallbacks.userData, &controller));
+    }
+
+    PxControllerBehaviorFlags getBehaviorFlags(const PxObstacle &obstacle) override {
+        return PxControllerBehaviorFlags(mCallbacks.getObstacleBehaviorFlags(mCallbacks.userData, &obstacle));
+    }
+
+    ControllerBehaviorCallbacks mCallbacks;
+};
+
+// The callbacks a controller was created with are only stored in the internal controller object.
+static const Cct::Controller *getCctController(const PxController *controller) {
+    switch (controller->getType()) {
+        case PxControllerShapeType::eBOX:
+            return static_cast<const Cct::BoxController *>(controller);
+        case PxControllerShapeType::eCAPSULE:
+            return static_cast<const Cct::CapsuleController *>(controller);
+        default:
+            return nullptr;
+    }
+}
+
+// The joint frames stored in the constant block of a joint are relative to the center of mass of each actor.
+static PxTransform getConstraintBodyTransform(const PxRigidActor *actor) {
+    if (!actor) {
+        return PxTransform(PxIdentity);
+    }
+    const PxRigidBody *body = actor->is<PxRigidBody>();
+    return body ? actor->getGlobalPose() * body->getCMassLocalPose() : actor->getGlobalPose();
+}
+
 typedef PxAgain (*RaycastHitProcessTouchesCallback)(const PxRaycastHit *buffer, PxU32 nbHits, void *userdata);
 
 typedef PxAgain (*SweepHitProcessTouchesCallback)(const PxSweepHit *buffer, PxU32 nbHits, void *userdata);
@@ -420,6 +598,53 @@ extern "C"
         _self->positions = (PxVec4 *) value;
     }
 
+    void PxTriangleMeshDesc_setMaterialIndices(PxTriangleMeshDesc *_address, const PxMaterialTableIndex *value, PxU32 stride) {
+        _address->materialIndices.data = value;
+        _address->materialIndices.stride = stride;
+    }
+
+    const PxMaterialTableIndex *PxTriangleMeshDesc_getMaterialIndices(const PxTriangleMeshDesc *_address, PxU32 *stride) {
+        *stride = _address->materialIndices.stride;
+        return _address->materialIndices.data;
+    }
+
+    void PxCookingParams_setMidphaseBVH33(PxCookingParams *_address, PxU32 meshCookingHint, PxReal meshSizePerformanceTradeOff) {
+        _address->midphaseDesc.setToDefault(PxMeshMidPhase::eBVH33);
+        _address->midphaseDesc.mBVH33Desc.meshCookingHint = static_cast<PxMeshCookingHint::Enum>(meshCookingHint);
+        _address->midphaseDesc.mBVH33Desc.meshSizePerformanceTradeOff = meshSizePerformanceTradeOff;
+    }
+
+    void PxCookingParams_getMidphaseBVH33(const PxCookingParams *_address, PxU32 *meshCookingHint, PxReal *meshSizePerformanceTradeOff) {
+        *meshCookingHint = _address->midphaseDesc.mBVH33Desc.meshCookingHint;
+        *meshSizePerformanceTradeOff = _address->midphaseDesc.mBVH33Desc.meshSizePerformanceTradeOff;
+    }
+
+    void PxCookingParams_setMidphaseBVH34(PxCookingParams *_address, const PxBVH34MidphaseDesc *desc) {
+        _address->midphaseDesc.setToDefault(PxMeshMidPhase::eBVH34);
+        _address->midphaseDesc.mBVH34Desc = *desc;
+    }
+
+    void PxCookingParams_getMidphaseBVH34(const PxCookingParams *_address, PxBVH34MidphaseDesc *desc) {
+        *desc = _address->midphaseDesc.mBVH34Desc;
+    }
+
+    bool PxTriangleMesh_canRefitBVH(const PxTriangleMesh *mesh) {
+        if (mesh->getConcreteType() == PxConcreteType::eTRIANGLE_MESH_BVH34) {
+            return !static_cast<const Gu::BV4TriangleMesh *>(mesh)->getBV4Tree().mQuantized;
+        }
+        return true;
+    }
+
+    PxUserControllerHitReport *PxController_getReportCallback(const PxController *controller) {
+        const Cct::Controller *cct = getCctController(controller);
+        return cct ? cct->mReportCallback : nullptr;
+    }
+
+    PxControllerBehaviorCallback *PxController_getBehaviorCallback(const PxController *controller) {
+        const Cct::Controller *cct = getCctController(controller);
+        return cct ? cct->mBehaviorCallback : nullptr;
+    }
+
     PxU32 *alloc_pinned_host_buffer_pxu32(PxCudaContextManager *cudaContextManager, PxU32 numElements) {
         return cudaContextManager->allocPinnedHostBuffer<PxU32>(numElements);
     }
@@ -465,6 +690,38 @@ extern "C"
         return new RaycastFilterTrampoline(callback, userData);
     }
 
+    PxQueryFilterCallback *create_query_filter_callback(QueryPreFilterCallback preFilter,
+                                                        QueryPostFilterCallback postFilter, void *userData) {
+        return new QueryFilterTrampoline(preFilter, postFilter, userData);
+    }
+
+    PxConstraintVisualizer *create_constraint_visualizer(const ConstraintVisualizerCallbacks *callbacks) {
+        return new ConstraintVisualizerTrampoline(callbacks);
+    }
+
+    void destroy_constraint_visualizer(PxConstraintVisualizer *visualizer) {
+        delete static_cast<ConstraintVisualizerTrampoline *>(visualizer);
+    }
+
+    // Runs the visualization function the joint was created with, which is not reachable through the public API.
+    bool PxJoint_visualize(const PxJoint *joint, PxConstraintVisualizer *visualizer, PxU32 flags) {
+        const NpConstraint *constraint = static_cast<const NpConstraint *>(joint->getConstraint());
+        if (!constraint) {
+            return false;
+        }
+        const Sc::ConstraintCore &core = constraint->getCore();
+        PxConstraintConnector *connector = core.getPxConnector();
+        PxConstraintVisualize visualize = core.getVisualize();
+        if (!connector || !visualize) {
+            return false;
+        }
+        PxRigidActor *actor0, *actor1;
+        joint->getActors(actor0, actor1);
+        visualize(*visualizer, connector->getConstantBlock(), getConstraintBodyTransform(actor0),
+                  getConstraintBodyTransform(actor1), flags);
+        return true;
+    }
+
     PxRaycastCallback *create_raycast_buffer() {
         return new PxRaycastBuffer;
     }
@@ -579,6 +836,56 @@ extern "C"
         delete trampoline;
     }
 
+    PxContactModifyCallback *create_contact_modify_callback(ContactModifyCallback callback,
+                                                            ContactModifyCallback ccdCallback, void *userData) {
+        return new ContactModifyTrampoline(callback, ccdCallback, userData);
+    }
+
+    PxCCDContactModifyCallback *get_ccd_contact_modify_callback(PxContactModifyCallback *callback) {
+        return static_cast<ContactModifyTrampoline *>(callback);
+    }
+
+    // Returns null if the callback was not created by create_contact_modify_callback.
+    void *get_contact_modify_user_data(PxContactModifyCallback *callback) {
+        ContactModifyTrampoline *trampoline = dynamic_cast<ContactModifyTrampoline *>(callback);
+        return trampoline ? trampoline->mUserData : nullptr;
+    }
+
+    void destroy_contact_modify_callback(PxContactModifyCallback *callback) {
+        delete static_cast<ContactModifyTrampoline *>(callback);
+    }
+
+    PxUserControllerHitReport *create_controller_hit_report(const ControllerHitReportCallbacks *callbacks) {
+        return new ControllerHitReportTrampoline(callbacks);
+    }
+
+    // Does nothing for reports not created by create_controller_hit_report.
+    void destroy_controller_hit_report(PxUserControllerHitReport *report) {
+        delete dynamic_cast<ControllerHitReportTrampoline *>(report);
+    }
+
+    PxControllerBehaviorCallback *create_controller_behavior_callback(const ControllerBehaviorCallbacks *callbacks) {
+        return new ControllerBehaviorTrampoline(callbacks);
+    }
+
+    // Does nothing for callbacks not created by create_controller_behavior_callback.
+    void destroy_controller_behavior_callback(PxControllerBehaviorCallback *callback) {
+        delete dynamic_cast<ControllerBehaviorTrampoline *>(callback);
+    }
+
+    // PxSerialization::createCollectionFromXml still takes the deprecated PxCooking to cook the meshes in the
+    // collection, so create one for the duration of the call.
+    PxCollection *create_collection_from_xml(PxInputData *inputData, const PxCookingParams *params,
+                                             PxSerializationRegistry *sr, const PxCollection *externalRefs) {
+        PxCooking *cooking = PxCreateCooking(PX_PHYSICS_VERSION, PxGetFoundation(), *params);
+        if (!cooking) {
+            return nullptr;
+        }
+        PxCollection *collection = PxSerialization::createCollectionFromXml(*inputData, *cooking, *sr, externalRefs);
+        cooking->release();
+        return collection;
+    }
+
     void enable_custom_filter_shader(PxSceneDesc *desc, SimulationShaderFilter filter,
                                      uint32_t call_default_filter_shader_first) {
         /* Note: This is a workaround to PhysX copying the filter data */
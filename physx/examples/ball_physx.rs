@@ -37,11 +37,11 @@ type PxScene = physx::scene::PxScene<
 /// Next up, the simulation event callbacks need to be defined, and possibly an
 /// allocator callback as well.
 struct OnCollision;
-impl CollisionCallback for OnCollision {
+impl CollisionCallback<PxArticulationLink, PxRigidStatic, PxRigidDynamic> for OnCollision {
     fn on_collision(
         &mut self,
-        _header: &physx_sys::PxContactPairHeader,
-        _pairs: &[physx_sys::PxContactPair],
+        _header: &ContactPairHeader<PxArticulationLink, PxRigidStatic, PxRigidDynamic>,
+        _pairs: &[ContactPair<PxArticulationLink, PxRigidStatic, PxRigidDynamic>],
     ) {
     }
 }
@@ -0,0 +1,403 @@
+//! Batched scene queries, for issuing many raycasts, sweeps and overlaps at once
+//! into buffers that are allocated once and reused.
+
+use crate::{
+    geometry::Geometry,
+    math::{PxTransform, PxVec3},
+    rigid_actor::RigidActor,
+    scene::Scene,
+    scene_query::{
+        EmptyHit, OverlapHit, QueryFlags, QueryHitType, QueryMode, QueryOptions, RaycastHit,
+        SweepHit,
+    },
+    traits::Class,
+};
+
+use std::{ffi::c_void, marker::PhantomData, ptr::null};
+
+#[rustfmt::skip]
+use physx_sys::{
+    create_query_filter_callback,
+    phys_PxCreateBatchQueryExt_1,
+    PxBatchQueryExt,
+    PxBatchQueryExt_execute_mut,
+    PxBatchQueryExt_overlap_mut,
+    PxBatchQueryExt_raycast_mut,
+    PxBatchQueryExt_release_mut,
+    PxBatchQueryExt_sweep_mut,
+    PxOverlapBuffer,
+    PxFilterData,
+    PxHitFlags,
+    PxOverlapHit,
+    PxQueryFilterCallback,
+    PxQueryFilterCallback_delete,
+    PxQueryFilterData,
+    PxQueryHit,
+    PxRaycastBuffer,
+    PxRaycastHit,
+    PxRigidActor,
+    PxScene,
+    PxShape,
+    PxShape_getQueryFilterData,
+    PxSweepBuffer,
+    PxSweepHit,
+};
+
+/// The number of queries, and hits shared between them, a [`BatchQuery`] has room for.
+#[derive(Default, Copy, Clone, Debug)]
+pub struct BatchQueryLimits {
+    pub max_raycasts: u32,
+    pub max_raycast_touches: u32,
+    pub max_sweeps: u32,
+    pub max_sweep_touches: u32,
+    pub max_overlaps: u32,
+    pub max_overlap_touches: u32,
+}
+
+/// A batch of scene queries against a single scene.
+///
+/// Queries are queued with [`raycast`](Self::raycast), [`sweep`](Self::sweep) and
+/// [`overlap`](Self::overlap), and all run by [`execute`](Self::execute), which returns the
+/// results of every query queued since the last execution.  The hits of all queries of a kind
+/// share one buffer, so queries late in the batch may get fewer hits than asked for.
+///
+/// PhysX disables its hardcoded filter for batched queries, so the `layers` of the query
+/// options are applied by a pre-filter of the batch instead.
+pub struct BatchQuery {
+    obj: *mut PxBatchQueryExt,
+    filter: *mut PxQueryFilterCallback,
+    scene: *const PxScene,
+    raycast_buffers: Vec<PxRaycastBuffer>,
+    sweep_buffers: Vec<PxSweepBuffer>,
+    overlap_buffers: Vec<PxOverlapBuffer>,
+    // Only accessed by PhysX, through the pointers handed over on creation.
+    _touches: (Vec<PxRaycastHit>, Vec<PxSweepHit>, Vec<PxOverlapHit>),
+    nb_raycasts: usize,
+    nb_sweeps: usize,
+    nb_overlaps: usize,
+}
+
+unsafe impl Send for BatchQuery {}
+unsafe impl Sync for BatchQuery {}
+
+impl Drop for BatchQuery {
+    fn drop(&mut self) {
+        unsafe {
+            PxBatchQueryExt_release_mut(self.obj);
+            PxQueryFilterCallback_delete(self.filter);
+        }
+    }
+}
+
+impl BatchQuery {
+    /// Create a batch query for `scene`.  Returns `None` if a kind of query has room for
+    /// touches but not for any queries.
+    pub fn new(scene: &impl Scene, limits: BatchQueryLimits) -> Option<Self> {
+        if (limits.max_raycast_touches != 0 && limits.max_raycasts == 0)
+            || (limits.max_sweep_touches != 0 && limits.max_sweeps == 0)
+            || (limits.max_overlap_touches != 0 && limits.max_overlaps == 0)
+        {
+            return None;
+        }
+
+        // Safety: the result buffers are plain old data, PhysX only ever writes their fields.
+        let mut raycast_buffers: Vec<PxRaycastBuffer> = (0..limits.max_raycasts)
+            .map(|_| unsafe { std::mem::zeroed() })
+            .collect();
+        let mut sweep_buffers: Vec<PxSweepBuffer> = (0..limits.max_sweeps)
+            .map(|_| unsafe { std::mem::zeroed() })
+            .collect();
+        let mut overlap_buffers: Vec<PxOverlapBuffer> = (0..limits.max_overlaps)
+            .map(|_| unsafe { std::mem::zeroed() })
+            .collect();
+        let mut raycast_touches = vec![PxRaycastHit::empty(); limits.max_raycast_touches as usize];
+        let mut sweep_touches = vec![PxSweepHit::empty(); limits.max_sweep_touches as usize];
+        let mut overlap_touches = vec![PxOverlapHit::empty(); limits.max_overlap_touches as usize];
+
+        let filter = unsafe {
+            create_query_filter_callback(layer_pre_filter, block_post_filter, std::ptr::null_mut())
+        };
+        let obj = unsafe {
+            phys_PxCreateBatchQueryExt_1(
+                scene.as_ptr(),
+                filter,
+                raycast_buffers.as_mut_ptr(),
+                limits.max_raycasts,
+                raycast_touches.as_mut_ptr(),
+                limits.max_raycast_touches,
+                sweep_buffers.as_mut_ptr(),
+                limits.max_sweeps,
+                sweep_touches.as_mut_ptr(),
+                limits.max_sweep_touches,
+                overlap_buffers.as_mut_ptr(),
+                limits.max_overlaps,
+                overlap_touches.as_mut_ptr(),
+                limits.max_overlap_touches,
+            )
+        };
+
+        if obj.is_null() {
+            unsafe { PxQueryFilterCallback_delete(filter) };
+            return None;
+        }
+
+        Some(Self {
+            obj,
+            filter,
+            scene: scene.as_ptr(),
+            raycast_buffers,
+            sweep_buffers,
+            overlap_buffers,
+            _touches: (raycast_touches, sweep_touches, overlap_touches),
+            nb_raycasts: 0,
+            nb_sweeps: 0,
+            nb_overlaps: 0,
+        })
+    }
+
+    /// Queue a raycast.  Returns the index of its results, or `None` if the batch is full.
+    pub fn raycast(
+        &mut self,
+        origin: &PxVec3,
+        unit_dir: &PxVec3,
+        distance: f32,
+        options: &QueryOptions,
+    ) -> Option<usize> {
+        if self.nb_raycasts == self.raycast_buffers.len() {
+            return None;
+        }
+        unsafe {
+            PxBatchQueryExt_raycast_mut(
+                self.obj,
+                origin.as_ptr(),
+                unit_dir.as_ptr(),
+                distance,
+                max_touches(options),
+                options.hit_flags,
+                &filter_data(options),
+                null(),
+            );
+        }
+        self.nb_raycasts += 1;
+        Some(self.nb_raycasts - 1)
+    }
+
+    /// Queue a sweep.  Returns the index of its results, or `None` if the batch is full.
+    pub fn sweep(
+        &mut self,
+        geometry: &impl Geometry,
+        pose: &PxTransform,
+        unit_dir: &PxVec3,
+        distance: f32,
+        options: &QueryOptions,
+    ) -> Option<usize> {
+        if self.nb_sweeps == self.sweep_buffers.len() {
+            return None;
+        }
+        unsafe {
+            PxBatchQueryExt_sweep_mut(
+                self.obj,
+                geometry.as_ptr(),
+                pose.as_ptr(),
+                unit_dir.as_ptr(),
+                distance,
+                max_touches(options),
+                options.hit_flags,
+                &filter_data(options),
+                null(),
+                options.inflation,
+            );
+        }
+        self.nb_sweeps += 1;
+        Some(self.nb_sweeps - 1)
+    }
+
+    /// Queue an overlap.  Returns the index of its results, or `None` if the batch is full.
+    pub fn overlap(
+        &mut self,
+        geometry: &impl Geometry,
+        pose: &PxTransform,
+        options: &QueryOptions,
+    ) -> Option<usize> {
+        if self.nb_overlaps == self.overlap_buffers.len() {
+            return None;
+        }
+        unsafe {
+            PxBatchQueryExt_overlap_mut(
+                self.obj,
+                geometry.as_ptr(),
+                pose.as_ptr(),
+                max_touches(options),
+                &filter_data(options),
+                null(),
+            );
+        }
+        self.nb_overlaps += 1;
+        Some(self.nb_overlaps - 1)
+    }
+
+    /// Run every queued query against `scene`, which must be the scene the batch was created for.
+    ///
+    /// # Panics
+    /// If `scene` is not the scene this batch query was created for.
+    pub fn execute<'a, S: Scene>(&'a mut self, scene: &'a S) -> BatchQueryResults<'a, S::ActorMap> {
+        assert_eq!(
+            self.scene,
+            scene.as_ptr(),
+            "BatchQuery executed against a different scene than it was created for"
+        );
+        unsafe { PxBatchQueryExt_execute_mut(self.obj) };
+
+        let results = BatchQueryResults {
+            raycasts: &self.raycast_buffers[..self.nb_raycasts],
+            sweeps: &self.sweep_buffers[..self.nb_sweeps],
+            overlaps: &self.overlap_buffers[..self.nb_overlaps],
+            phantom: PhantomData,
+        };
+        self.nb_raycasts = 0;
+        self.nb_sweeps = 0;
+        self.nb_overlaps = 0;
+        results
+    }
+}
+
+/// The results of an executed [`BatchQuery`], indexed by the values returned when queueing.
+/// Hits are reported in the same way as for [`QueryMode`].
+pub struct BatchQueryResults<'a, A: RigidActor> {
+    raycasts: &'a [PxRaycastBuffer],
+    sweeps: &'a [PxSweepBuffer],
+    overlaps: &'a [PxOverlapBuffer],
+    phantom: PhantomData<&'a A>,
+}
+
+impl<'a, A: RigidActor> BatchQueryResults<'a, A> {
+    /// The number of raycasts that were executed.
+    pub fn nb_raycasts(&self) -> usize {
+        self.raycasts.len()
+    }
+
+    /// The number of sweeps that were executed.
+    pub fn nb_sweeps(&self) -> usize {
+        self.sweeps.len()
+    }
+
+    /// The number of overlaps that were executed.
+    pub fn nb_overlaps(&self) -> usize {
+        self.overlaps.len()
+    }
+
+    /// The hits of a raycast.
+    ///
+    /// # Panics
+    /// If `index` is not less than `nb_raycasts()`.
+    pub fn raycast(&self, index: usize) -> impl Iterator<Item = RaycastHit<'a, A>> {
+        let buffer = &self.raycasts[index];
+        unsafe {
+            raw_hits(
+                buffer.hasBlock,
+                &buffer.block,
+                buffer.touches,
+                buffer.nbTouches,
+            )
+        }
+        .map(|hit| unsafe { RaycastHit::from_raw(hit) })
+    }
+
+    /// The hits of a sweep.
+    ///
+    /// # Panics
+    /// If `index` is not less than `nb_sweeps()`.
+    pub fn sweep(&self, index: usize) -> impl Iterator<Item = SweepHit<'a, A>> {
+        let buffer = &self.sweeps[index];
+        unsafe {
+            raw_hits(
+                buffer.hasBlock,
+                &buffer.block,
+                buffer.touches,
+                buffer.nbTouches,
+            )
+        }
+        .map(|hit| unsafe { SweepHit::from_raw(hit) })
+    }
+
+    /// The hits of an overlap.
+    ///
+    /// # Panics
+    /// If `index` is not less than `nb_overlaps()`.
+    pub fn overlap(&self, index: usize) -> impl Iterator<Item = OverlapHit<'a, A>> {
+        let buffer = &self.overlaps[index];
+        unsafe {
+            raw_hits(
+                buffer.hasBlock,
+                &buffer.block,
+                buffer.touches,
+                buffer.nbTouches,
+            )
+        }
+        .map(|hit| unsafe { OverlapHit::from_raw(hit) })
+    }
+}
+
+/// Filter data of a batched query, with the pre-filter enabled when it has layers to apply.
+fn filter_data(options: &QueryOptions) -> PxQueryFilterData {
+    let mut filter_data = options.filter_data(false);
+    if !options.layers.is_empty() {
+        filter_data.flags |= QueryFlags::Prefilter;
+    }
+    filter_data
+}
+
+/// The hardcoded filter equation of PhysX, for the layers in word0 of the query filter data.
+unsafe extern "C" fn layer_pre_filter(
+    filter_data: *const PxFilterData,
+    shape: *const PxShape,
+    _actor: *const PxRigidActor,
+    _hit_flags: *mut PxHitFlags,
+    _user_data: *mut c_void,
+) -> QueryHitType {
+    unsafe {
+        let layers = (*filter_data).word0;
+        if layers == 0 || layers & PxShape_getQueryFilterData(shape).word0 != 0 {
+            QueryHitType::Block
+        } else {
+            QueryHitType::None
+        }
+    }
+}
+
+/// Only called if the query options ask for post-filtering, which keeps every hit.
+unsafe extern "C" fn block_post_filter(
+    _filter_data: *const PxFilterData,
+    _hit: *const PxQueryHit,
+    _shape: *const PxShape,
+    _actor: *const PxRigidActor,
+    _user_data: *mut c_void,
+) -> QueryHitType {
+    QueryHitType::Block
+}
+
+/// The number of touches to ask PhysX for, touch buffers are limited to u16::MAX hits per query.
+fn max_touches(options: &QueryOptions) -> u16 {
+    match options.mode {
+        QueryMode::Multiple { max_hits } => max_hits.min(u16::MAX as u32) as u16,
+        QueryMode::Closest | QueryMode::Any => 0,
+    }
+}
+
+/// The blocking hit, if any, followed by the touching hits of a query result buffer.
+///
+/// # Safety
+/// `touches` must point to `nb_touches` hits if `nb_touches` is not zero.
+unsafe fn raw_hits<'a, H>(
+    has_block: bool,
+    block: &'a H,
+    touches: *const H,
+    nb_touches: u32,
+) -> impl Iterator<Item = &'a H> {
+    let touches: &'a [H] = if nb_touches == 0 || touches.is_null() {
+        &[]
+    } else {
+        unsafe { std::slice::from_raw_parts(touches, nb_touches as usize) }
+    };
+    has_block.then_some(block).into_iter().chain(touches)
+}
@@ -0,0 +1,406 @@
+//! Serialization of PhysX objects to binary and RepX XML.
+//!
+//! A [`Collection`] is a set of objects that get serialized together.  Objects
+//! may reference objects outside of the collection if those are passed as
+//! `external_references` both when serializing and deserializing.
+
+use crate::{
+    base::{Base, PxBase},
+    cooking::PxCookingParams,
+    owner::Owner,
+    physics::Physics,
+    scene::Scene,
+    stream::{read_from_slice, write_to_vec},
+    traits::Class,
+};
+
+use std::{
+    alloc::{alloc, dealloc, Layout, LayoutError},
+    io::Write,
+    ptr::{null, null_mut},
+};
+
+use thiserror::Error;
+
+#[rustfmt::skip]
+use physx_sys::{
+    create_collection_from_xml,
+    phys_PxCreateCollection,
+    PxActor,
+    PxActorTypeFlags,
+    PxArticulationReducedCoordinate,
+    PxCollection_add_mut,
+    PxCollection_add_mut_1,
+    PxCollection_contains,
+    PxCollection_find,
+    PxCollection_getId,
+    PxCollection_getNbObjects,
+    PxCollection_getObject,
+    PxCollection_release_mut,
+    PxCollection_remove_mut,
+    PxCollection_remove_mut_1,
+    PxScene_getActors,
+    PxScene_getArticulations,
+    PxScene_getNbActors,
+    PxScene_getNbArticulations,
+    PxSerializationRegistry_release_mut,
+    PxSerialization_complete,
+    PxSerialization_createCollectionFromBinary,
+    PxSerialization_createSerialObjectIds,
+    PxSerialization_createSerializationRegistry,
+    PxSerialization_isSerializable,
+    PxSerialization_serializeCollectionToBinary,
+    PxSerialization_serializeCollectionToXml,
+};
+
+/// Binary data must be deserialized from memory with this alignment.
+const BINARY_ALIGNMENT: usize = 128;
+
+#[derive(Error, Debug)]
+pub enum SerializationError {
+    #[error("The collection is not serializable, it may be missing objects its members depend on")]
+    NotSerializable,
+
+    #[error("PhysX failed to serialize the collection")]
+    SerializationFailed,
+
+    #[error("PhysX failed to deserialize the collection, the data may be corrupt or from another PhysX version")]
+    DeserializationFailed,
+
+    #[error("Failed to write the serialized collection")]
+    Io(#[from] std::io::Error),
+
+    #[error("The serialized data is too large to be copied into aligned memory")]
+    Layout(#[from] LayoutError),
+}
+
+/// A new type wrapper for `PxSerializationRegistry`, which holds the serializers for
+/// all the object types that can be serialized.
+pub struct SerializationRegistry {
+    obj: physx_sys::PxSerializationRegistry,
+}
+
+crate::DeriveClassForNewType!(SerializationRegistry: PxSerializationRegistry);
+
+impl SerializationRegistry {
+    /// Create a registry with serializers for the PhysX and extension types.  Deserialized
+    /// objects are created through `physics`.
+    pub fn new(physics: &mut impl Physics) -> Option<Owner<Self>> {
+        unsafe {
+            Owner::from_raw(
+                PxSerialization_createSerializationRegistry(physics.as_mut_ptr()) as *mut Self,
+            )
+        }
+    }
+}
+
+unsafe impl Send for SerializationRegistry {}
+unsafe impl Sync for SerializationRegistry {}
+
+impl Drop for SerializationRegistry {
+    fn drop(&mut self) {
+        unsafe { PxSerializationRegistry_release_mut(self.as_mut_ptr()) }
+    }
+}
+
+/// A new type wrapper for `PxCollection`.  The collection references its objects, it does
+/// not own them: dropping it does not release them.
+#[repr(transparent)]
+pub struct Collection {
+    obj: physx_sys::PxCollection,
+}
+
+crate::DeriveClassForNewType!(Collection: PxCollection);
+
+impl Collection {
+    /// Create an empty collection.
+    pub fn new() -> Option<Owner<Self>> {
+        unsafe { Self::from_raw(phys_PxCreateCollection()) }
+    }
+
+    /// # Safety
+    /// Owner's own the pointer they wrap, using the pointer after dropping the Owner,
+    /// or creating multiple Owners from the same pointer will cause UB.  Use `into_ptr` to
+    /// retrieve the pointer and consume the Owner without dropping the pointee.
+    pub unsafe fn from_raw(ptr: *mut physx_sys::PxCollection) -> Option<Owner<Self>> {
+        unsafe { Owner::from_raw(ptr as *mut Self) }
+    }
+
+    /// Create a collection of the rigid actors and articulations in the scene, completed with
+    /// everything they depend on: shapes, materials, meshes and the joints between them.
+    pub fn from_scene(
+        scene: &mut impl Scene,
+        registry: &mut SerializationRegistry,
+    ) -> Option<Owner<Self>> {
+        let mut collection = Self::new()?;
+        unsafe {
+            let actor_types = PxActorTypeFlags::RigidStatic | PxActorTypeFlags::RigidDynamic;
+            let capacity = PxScene_getNbActors(scene.as_ptr(), actor_types);
+            let mut actors: Vec<*mut PxActor> = Vec::with_capacity(capacity as usize);
+            let len = PxScene_getActors(
+                scene.as_ptr(),
+                actor_types,
+                actors.as_mut_ptr(),
+                capacity,
+                0,
+            );
+            actors.set_len(len as usize);
+            for actor in actors {
+                PxCollection_add_mut(collection.as_mut_ptr(), actor as *mut PxBase, 0);
+            }
+
+            let capacity = PxScene_getNbArticulations(scene.as_ptr());
+            let mut articulations: Vec<*mut PxArticulationReducedCoordinate> =
+                Vec::with_capacity(capacity as usize);
+            let len =
+                PxScene_getArticulations(scene.as_ptr(), articulations.as_mut_ptr(), capacity, 0);
+            articulations.set_len(len as usize);
+            for articulation in articulations {
+                PxCollection_add_mut(collection.as_mut_ptr(), articulation as *mut PxBase, 0);
+            }
+        }
+        collection.complete(registry, None, true);
+        Some(collection)
+    }
+
+    /// Add an object to the collection, optionally with an id to find it by after deserialization.
+    /// Ids must be unique within the collection.
+    pub fn add(&mut self, object: &mut impl Base, id: Option<u64>) {
+        unsafe { PxCollection_add_mut(self.as_mut_ptr(), object.as_mut_ptr(), id.unwrap_or(0)) }
+    }
+
+    /// Remove an object from the collection.
+    pub fn remove(&mut self, object: &mut impl Base) {
+        unsafe { PxCollection_remove_mut(self.as_mut_ptr(), object.as_mut_ptr()) }
+    }
+
+    /// Returns true if the object is in the collection.
+    pub fn contains(&self, object: &impl Base) -> bool {
+        unsafe { PxCollection_contains(self.as_ptr(), object.as_ptr() as *mut _) }
+    }
+
+    /// Add all the objects and ids of another collection to this one.
+    pub fn add_collection(&mut self, other: &mut Collection) {
+        unsafe { PxCollection_add_mut_1(self.as_mut_ptr(), other.as_mut_ptr()) }
+    }
+
+    /// Remove all the objects of another collection from this one.
+    pub fn remove_collection(&mut self, other: &mut Collection) {
+        unsafe { PxCollection_remove_mut_1(self.as_mut_ptr(), other.as_mut_ptr()) }
+    }
+
+    /// Get the id of an object, or None if it is not in the collection or has no id.
+    pub fn get_id(&self, object: &impl Base) -> Option<u64> {
+        match unsafe { PxCollection_getId(self.as_ptr(), object.as_ptr()) } {
+            0 => None,
+            id => Some(id),
+        }
+    }
+
+    /// Find an object by its id.  Use [`Base::get_concrete_type`] to find out what it is.
+    pub fn find(&mut self, id: u64) -> Option<&mut PxBase> {
+        unsafe { PxCollection_find(self.as_ptr(), id).as_mut() }
+    }
+
+    /// Get the objects in the collection.
+    pub fn get_objects(&mut self) -> Vec<&mut PxBase> {
+        unsafe {
+            (0..PxCollection_getNbObjects(self.as_ptr()))
+                .filter_map(|index| PxCollection_getObject(self.as_ptr(), index).as_mut())
+                .collect()
+        }
+    }
+
+    /// Get the number of objects in the collection.
+    pub fn len(&self) -> usize {
+        unsafe { PxCollection_getNbObjects(self.as_ptr()) as usize }
+    }
+
+    /// Returns true if the collection has no objects.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Add all the objects the members of the collection depend on, except for those in `except_for`.
+    /// If `follow_joints` is set, joints attached to actors in the collection and the actors on their
+    /// other end are added as well.
+    pub fn complete(
+        &mut self,
+        registry: &mut SerializationRegistry,
+        except_for: Option<&Collection>,
+        follow_joints: bool,
+    ) {
+        unsafe {
+            PxSerialization_complete(
+                self.as_mut_ptr(),
+                registry.as_mut_ptr(),
+                collection_ptr(except_for),
+                follow_joints,
+            )
+        }
+    }
+
+    /// Give every object in the collection without an id one, counting up from `base`.
+    pub fn create_serial_object_ids(&mut self, base: u64) {
+        unsafe { PxSerialization_createSerialObjectIds(self.as_mut_ptr(), base) }
+    }
+
+    /// Returns true if everything the collection depends on is either in the collection or in
+    /// `external_references`, and all external references have ids.
+    pub fn is_serializable(
+        &mut self,
+        registry: &mut SerializationRegistry,
+        external_references: Option<&Collection>,
+    ) -> bool {
+        unsafe {
+            PxSerialization_isSerializable(
+                self.as_mut_ptr(),
+                registry.as_mut_ptr(),
+                collection_ptr(external_references),
+            )
+        }
+    }
+
+    /// Serialize the collection in the binary format, which is fast to load but specific to the
+    /// PhysX version and platform.  `export_names` includes the names of actors and shapes.
+    pub fn serialize_binary(
+        &mut self,
+        registry: &mut SerializationRegistry,
+        external_references: Option<&Collection>,
+        export_names: bool,
+        writer: &mut impl Write,
+    ) -> Result<(), SerializationError> {
+        if !self.is_serializable(registry, external_references) {
+            return Err(SerializationError::NotSerializable);
+        }
+        let data = write_to_vec(|stream| unsafe {
+            PxSerialization_serializeCollectionToBinary(
+                stream,
+                self.as_mut_ptr(),
+                registry.as_mut_ptr(),
+                collection_ptr(external_references),
+                export_names,
+            )
+        })
+        .ok_or(SerializationError::SerializationFailed)?;
+        Ok(writer.write_all(&data)?)
+    }
+
+    /// Serialize the collection in the RepX XML format, which is portable across PhysX versions
+    /// and platforms.  Meshes are stored as their source data and are cooked again on load.
+    pub fn serialize_xml(
+        &mut self,
+        registry: &mut SerializationRegistry,
+        external_references: Option<&Collection>,
+        writer: &mut impl Write,
+    ) -> Result<(), SerializationError> {
+        if !self.is_serializable(registry, external_references) {
+            return Err(SerializationError::NotSerializable);
+        }
+        let data = write_to_vec(|stream| unsafe {
+            PxSerialization_serializeCollectionToXml(
+                stream,
+                self.as_mut_ptr(),
+                registry.as_mut_ptr(),
+                null_mut(),
+                collection_ptr(external_references),
+                null_mut(),
+            )
+        })
+        .ok_or(SerializationError::SerializationFailed)?;
+        Ok(writer.write_all(&data)?)
+    }
+
+    /// Deserialize a collection written by [`Collection::serialize_binary`].  The objects are
+    /// created in the `Physics` the registry was created with, use
+    /// [`Scene::add_collection`] to add them to a scene.
+    ///
+    /// The objects live in an aligned copy of `data`, which is returned along with the
+    /// collection and freed when dropped.
+    ///
+    /// # Safety
+    /// PhysX only checks the header of the data and trusts the offsets and counts after it.
+    /// It must have been produced by `serialize_binary` with the same version of PhysX.  The
+    /// returned [`BinaryData`] must not be dropped before every deserialized object has been
+    /// released.
+    pub unsafe fn deserialize_binary(
+        registry: &mut SerializationRegistry,
+        external_references: Option<&Collection>,
+        data: &[u8],
+    ) -> Result<(Owner<Self>, BinaryData), SerializationError> {
+        let memory = BinaryData::new(data)?;
+        unsafe {
+            let collection = Self::from_raw(PxSerialization_createCollectionFromBinary(
+                memory.memory.cast(),
+                registry.as_mut_ptr(),
+                collection_ptr(external_references),
+            ));
+            collection
+                .map(|collection| (collection, memory))
+                .ok_or(SerializationError::DeserializationFailed)
+        }
+    }
+
+    /// Deserialize a collection written by [`Collection::serialize_xml`], cooking its meshes
+    /// with `params`.  The objects are created in the `Physics` the registry was created with,
+    /// use [`Scene::add_collection`] to add them to a scene.
+    pub fn deserialize_xml(
+        registry: &mut SerializationRegistry,
+        params: &PxCookingParams,
+        external_references: Option<&Collection>,
+        data: &[u8],
+    ) -> Result<Owner<Self>, SerializationError> {
+        read_from_slice(data, |input| unsafe {
+            Self::from_raw(create_collection_from_xml(
+                input,
+                params.as_ptr(),
+                registry.as_mut_ptr(),
+                collection_ptr(external_references),
+            ))
+        })
+        .ok_or(SerializationError::DeserializationFailed)
+    }
+}
+
+unsafe impl Send for Collection {}
+unsafe impl Sync for Collection {}
+
+/// The aligned copy of the data a collection was deserialized from with
+/// [`Collection::deserialize_binary`], which the deserialized objects live in.
+pub struct BinaryData {
+    memory: *mut u8,
+    layout: Layout,
+}
+
+impl BinaryData {
+    fn new(data: &[u8]) -> Result<Self, SerializationError> {
+        let layout = Layout::from_size_align(data.len().max(1), BINARY_ALIGNMENT)?;
+        unsafe {
+            let memory = alloc(layout);
+            if memory.is_null() {
+                std::alloc::handle_alloc_error(layout);
+            }
+            memory.copy_from_nonoverlapping(data.as_ptr(), data.len());
+            Ok(Self { memory, layout })
+        }
+    }
+}
+
+unsafe impl Send for BinaryData {}
+unsafe impl Sync for BinaryData {}
+
+impl Drop for BinaryData {
+    fn drop(&mut self) {
+        unsafe { dealloc(self.memory, self.layout) }
+    }
+}
+
+impl Drop for Collection {
+    fn drop(&mut self) {
+        unsafe { PxCollection_release_mut(self.as_mut_ptr()) }
+    }
+}
+
+fn collection_ptr(collection: Option<&Collection>) -> *const physx_sys::PxCollection {
+    collection.map_or(null(), |collection| collection.as_ptr())
+}
@@ -0,0 +1,390 @@
+use crate::{
+    actor::ActorMap,
+    articulation_link::ArticulationLink,
+    math::{PxTransform, PxVec3},
+    rigid_actor::RigidActor,
+    rigid_dynamic::RigidDynamic,
+    rigid_static::RigidStatic,
+    traits::Class,
+};
+
+use std::{ffi::c_void, marker::PhantomData, slice};
+
+#[rustfmt::skip]
+use physx_sys::{
+    create_contact_modify_callback,
+    destroy_contact_modify_callback,
+    get_contact_modify_user_data,
+    PxContactModifyCallback,
+    PxContactModifyPair,
+    PxContactSet,
+    PxContactSet_getDynamicFriction,
+    PxContactSet_getInternalFaceIndex0,
+    PxContactSet_getInternalFaceIndex1,
+    PxContactSet_getInvInertiaScale0,
+    PxContactSet_getInvInertiaScale1,
+    PxContactSet_getInvMassScale0,
+    PxContactSet_getInvMassScale1,
+    PxContactSet_getMaxImpulse,
+    PxContactSet_getNormal,
+    PxContactSet_getPoint,
+    PxContactSet_getRestitution,
+    PxContactSet_getSeparation,
+    PxContactSet_getStaticFriction,
+    PxContactSet_getTargetVelocity,
+    PxContactSet_ignore_mut,
+    PxContactSet_setDynamicFriction_mut,
+    PxContactSet_setInvInertiaScale0_mut,
+    PxContactSet_setInvInertiaScale1_mut,
+    PxContactSet_setInvMassScale0_mut,
+    PxContactSet_setInvMassScale1_mut,
+    PxContactSet_setMaxImpulse_mut,
+    PxContactSet_setNormal_mut,
+    PxContactSet_setPoint_mut,
+    PxContactSet_setRestitution_mut,
+    PxContactSet_setSeparation_mut,
+    PxContactSet_setStaticFriction_mut,
+    PxContactSet_setTargetVelocity_mut,
+    PxContactSet_size,
+};
+
+/// A trait for onContactModify() and onCCDContactModify().  Parametrized by the
+/// [`ArticulationLink`], [`RigidStatic`], and [`RigidDynamic`] types of the scene it is in.
+///
+/// The callbacks are called during simulation, possibly from several threads at once, so they
+/// must be thread safe, and `self` is not mutable.  Only pairs with `PairFlag::ModifyContacts`
+/// set by the filter shader are passed to the callbacks.
+pub trait ContactModifyCallback<L: ArticulationLink, S: RigidStatic, D: RigidDynamic>:
+    Sized + Sync
+{
+    fn on_contact_modify(&self, pairs: &mut [ContactModifyPair<L, S, D>]);
+
+    /// Called for contacts generated by continuous collision detection, if the scene was created
+    /// with [`SceneDescriptor::modify_ccd_contacts`](crate::traits::descriptor::SceneDescriptor::modify_ccd_contacts)
+    /// set.  Calls `on_contact_modify` by default.
+    fn on_ccd_contact_modify(&self, pairs: &mut [ContactModifyPair<L, S, D>]) {
+        self.on_contact_modify(pairs)
+    }
+}
+
+/// The contact modify callback type of scenes that do not modify contacts, and the default
+/// when the type parameter is left out.
+pub struct NoContactModify;
+
+impl<L, S, D> ContactModifyCallback<L, S, D> for NoContactModify
+where
+    L: ArticulationLink,
+    S: RigidStatic,
+    D: RigidDynamic,
+{
+    fn on_contact_modify(&self, _pairs: &mut [ContactModifyPair<L, S, D>]) {}
+}
+
+impl<T, L, S, D> ContactModifyCallbackRaw<L, S, D> for T
+where
+    T: ContactModifyCallback<L, S, D>,
+    L: ArticulationLink,
+    S: RigidStatic,
+    D: RigidDynamic,
+{
+}
+
+pub(crate) trait ContactModifyCallbackRaw<L, S, D>: ContactModifyCallback<L, S, D>
+where
+    L: ArticulationLink,
+    S: RigidStatic,
+    D: RigidDynamic,
+{
+    unsafe extern "C" fn callback(
+        this: *mut c_void,
+        pairs: *mut PxContactModifyPair,
+        nb_pairs: u32,
+    ) {
+        unsafe {
+            Self::on_contact_modify(
+                &*(this as *const Self),
+                slice::from_raw_parts_mut(
+                    pairs as *mut ContactModifyPair<L, S, D>,
+                    nb_pairs as usize,
+                ),
+            )
+        }
+    }
+
+    unsafe extern "C" fn ccd_callback(
+        this: *mut c_void,
+        pairs: *mut PxContactModifyPair,
+        nb_pairs: u32,
+    ) {
+        unsafe {
+            Self::on_ccd_contact_modify(
+                &*(this as *const Self),
+                slice::from_raw_parts_mut(
+                    pairs as *mut ContactModifyPair<L, S, D>,
+                    nb_pairs as usize,
+                ),
+            )
+        }
+    }
+
+    /// Box the callback into a PxContactModifyCallback, which is also a PxCCDContactModifyCallback.
+    fn into_raw_callback(self) -> *mut PxContactModifyCallback {
+        unsafe {
+            create_contact_modify_callback(
+                Self::callback,
+                Self::ccd_callback,
+                Box::into_raw(Box::new(self)) as *mut c_void,
+            )
+        }
+    }
+
+    /// Drop a callback created by `into_raw_callback`, does nothing for any other callback.
+    ///
+    /// # Safety
+    /// If `callback` was created by `into_raw_callback`, it must have been for this type.
+    unsafe fn drop_raw_callback(callback: *mut PxContactModifyCallback) {
+        unsafe {
+            let user_data = get_contact_modify_user_data(callback);
+            if !user_data.is_null() {
+                drop(Box::from_raw(user_data as *mut Self));
+                destroy_contact_modify_callback(callback);
+            }
+        }
+    }
+}
+
+/// A new type wrapper for PxContactModifyPair, which resolves the actors and shapes to the types
+/// of the scene, and gives access to the contacts of the pair.
+#[repr(transparent)]
+pub struct ContactModifyPair<L, S, D>
+where
+    L: ArticulationLink,
+    S: RigidStatic,
+    D: RigidDynamic,
+{
+    obj: PxContactModifyPair,
+    phantom_user_data: PhantomData<(*const L, *const S, *const D)>,
+}
+
+impl<L, S, D> ContactModifyPair<L, S, D>
+where
+    L: ArticulationLink,
+    S: RigidStatic,
+    D: RigidDynamic,
+{
+    /// Get the two actors in contact.
+    pub fn actors(&self) -> [&ActorMap<L, S, D>; 2] {
+        unsafe {
+            self.obj
+                .actor
+                .map(|actor| &*(actor as *const ActorMap<L, S, D>))
+        }
+    }
+
+    /// Get the two shapes in contact.
+    pub fn shapes(&self) -> [&<ActorMap<L, S, D> as RigidActor>::Shape; 2] {
+        unsafe {
+            self.obj
+                .shape
+                .map(|shape| &*(shape as *const <ActorMap<L, S, D> as RigidActor>::Shape))
+        }
+    }
+
+    /// Get the world transforms of the two shapes.
+    pub fn transforms(&self) -> [PxTransform; 2] {
+        self.obj.transform.map(PxTransform::from)
+    }
+
+    /// Get the contacts of the pair.
+    pub fn contacts(&self) -> &ContactSet {
+        unsafe { &*(&self.obj.contacts as *const PxContactSet as *const ContactSet) }
+    }
+
+    /// Get the contacts of the pair for modification.
+    pub fn contacts_mut(&mut self) -> &mut ContactSet {
+        unsafe { &mut *(&mut self.obj.contacts as *mut PxContactSet as *mut ContactSet) }
+    }
+}
+
+/// A new type wrapper for PxContactSet, the modifiable contacts of a [`ContactModifyPair`].
+/// Contacts are indexed from zero to `len()`, and all methods taking an index panic if it is
+/// out of range.
+#[repr(transparent)]
+pub struct ContactSet {
+    obj: PxContactSet,
+}
+
+impl ContactSet {
+    /// Get the number of contacts in the set.
+    pub fn len(&self) -> usize {
+        unsafe { PxContactSet_size(&self.obj) as usize }
+    }
+
+    /// Returns true if the set has no contacts.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn checked_index(&self, index: usize) -> u32 {
+        assert!(
+            index < self.len(),
+            "contact index {index} out of range for a contact set of {} contacts",
+            self.len()
+        );
+        index as u32
+    }
+
+    /// Get the position of a contact.
+    pub fn get_point(&self, index: usize) -> PxVec3 {
+        let index = self.checked_index(index);
+        unsafe { *PxContactSet_getPoint(&self.obj, index) }.into()
+    }
+
+    /// Set the position of a contact.
+    pub fn set_point(&mut self, index: usize, point: &PxVec3) {
+        let index = self.checked_index(index);
+        unsafe { PxContactSet_setPoint_mut(&mut self.obj, index, point.as_ptr()) }
+    }
+
+    /// Get the normal of a contact, pointing from the second shape towards the first.
+    pub fn get_normal(&self, index: usize) -> PxVec3 {
+        let index = self.checked_index(index);
+        unsafe { *PxContactSet_getNormal(&self.obj, index) }.into()
+    }
+
+    /// Set the normal of a contact, it must be normalized.
+    pub fn set_normal(&mut self, index: usize, normal: &PxVec3) {
+        let index = self.checked_index(index);
+        unsafe { PxContactSet_setNormal_mut(&mut self.obj, index, normal.as_ptr()) }
+    }
+
+    /// Get the separation of a contact, negative when the shapes penetrate.
+    pub fn get_separation(&self, index: usize) -> f32 {
+        let index = self.checked_index(index);
+        unsafe { PxContactSet_getSeparation(&self.obj, index) }
+    }
+
+    /// Set the separation of a contact.
+    pub fn set_separation(&mut self, index: usize, separation: f32) {
+        let index = self.checked_index(index);
+        unsafe { PxContactSet_setSeparation_mut(&mut self.obj, index, separation) }
+    }
+
+    /// Get the target relative velocity of a contact.
+    pub fn get_target_velocity(&self, index: usize) -> PxVec3 {
+        let index = self.checked_index(index);
+        unsafe { *PxContactSet_getTargetVelocity(&self.obj, index) }.into()
+    }
+
+    /// Set the target relative velocity of a contact, such as for conveyor belts.
+    pub fn set_target_velocity(&mut self, index: usize, velocity: &PxVec3) {
+        let index = self.checked_index(index);
+        unsafe { PxContactSet_setTargetVelocity_mut(&mut self.obj, index, velocity.as_ptr()) }
+    }
+
+    /// Get the internal face index of the first shape at a contact.
+    pub fn get_internal_face_index0(&self, index: usize) -> u32 {
+        let index = self.checked_index(index);
+        unsafe { PxContactSet_getInternalFaceIndex0(&self.obj, index) }
+    }
+
+    /// Get the internal face index of the second shape at a contact.
+    pub fn get_internal_face_index1(&self, index: usize) -> u32 {
+        let index = self.checked_index(index);
+        unsafe { PxContactSet_getInternalFaceIndex1(&self.obj, index) }
+    }
+
+    /// Get the maximum impulse that may be applied at a contact.
+    pub fn get_max_impulse(&self, index: usize) -> f32 {
+        let index = self.checked_index(index);
+        unsafe { PxContactSet_getMaxImpulse(&self.obj, index) }
+    }
+
+    /// Set the maximum impulse that may be applied at a contact.
+    pub fn set_max_impulse(&mut self, index: usize, max_impulse: f32) {
+        let index = self.checked_index(index);
+        unsafe { PxContactSet_setMaxImpulse_mut(&mut self.obj, index, max_impulse) }
+    }
+
+    /// Get the restitution of a contact.
+    pub fn get_restitution(&self, index: usize) -> f32 {
+        let index = self.checked_index(index);
+        unsafe { PxContactSet_getRestitution(&self.obj, index) }
+    }
+
+    /// Set the restitution of a contact.
+    pub fn set_restitution(&mut self, index: usize, restitution: f32) {
+        let index = self.checked_index(index);
+        unsafe { PxContactSet_setRestitution_mut(&mut self.obj, index, restitution) }
+    }
+
+    /// Get the static friction coefficient of a contact.
+    pub fn get_static_friction(&self, index: usize) -> f32 {
+        let index = self.checked_index(index);
+        unsafe { PxContactSet_getStaticFriction(&self.obj, index) }
+    }
+
+    /// Set the static friction coefficient of a contact.
+    pub fn set_static_friction(&mut self, index: usize, friction: f32) {
+        let index = self.checked_index(index);
+        unsafe { PxContactSet_setStaticFriction_mut(&mut self.obj, index, friction) }
+    }
+
+    /// Get the dynamic friction coefficient of a contact.
+    pub fn get_dynamic_friction(&self, index: usize) -> f32 {
+        let index = self.checked_index(index);
+        unsafe { PxContactSet_getDynamicFriction(&self.obj, index) }
+    }
+
+    /// Set the dynamic friction coefficient of a contact.
+    pub fn set_dynamic_friction(&mut self, index: usize, friction: f32) {
+        let index = self.checked_index(index);
+        unsafe { PxContactSet_setDynamicFriction_mut(&mut self.obj, index, friction) }
+    }
+
+    /// Ignore a contact, so it is not solved.
+    pub fn ignore(&mut self, index: usize) {
+        let index = self.checked_index(index);
+        unsafe { PxContactSet_ignore_mut(&mut self.obj, index) }
+    }
+
+    /// Get the inverse mass scale of the first actor, shared by all contacts of the pair.
+    pub fn get_inv_mass_scale0(&self) -> f32 {
+        unsafe { PxContactSet_getInvMassScale0(&self.obj) }
+    }
+
+    /// Set the inverse mass scale of the first actor.
+    pub fn set_inv_mass_scale0(&mut self, scale: f32) {
+        unsafe { PxContactSet_setInvMassScale0_mut(&mut self.obj, scale) }
+    }
+
+    /// Get the inverse mass scale of the second actor, shared by all contacts of the pair.
+    pub fn get_inv_mass_scale1(&self) -> f32 {
+        unsafe { PxContactSet_getInvMassScale1(&self.obj) }
+    }
+
+    /// Set the inverse mass scale of the second actor.
+    pub fn set_inv_mass_scale1(&mut self, scale: f32) {
+        unsafe { PxContactSet_setInvMassScale1_mut(&mut self.obj, scale) }
+    }
+
+    /// Get the inverse inertia scale of the first actor, shared by all contacts of the pair.
+    pub fn get_inv_inertia_scale0(&self) -> f32 {
+        unsafe { PxContactSet_getInvInertiaScale0(&self.obj) }
+    }
+
+    /// Set the inverse inertia scale of the first actor.
+    pub fn set_inv_inertia_scale0(&mut self, scale: f32) {
+        unsafe { PxContactSet_setInvInertiaScale0_mut(&mut self.obj, scale) }
+    }
+
+    /// Get the inverse inertia scale of the second actor, shared by all contacts of the pair.
+    pub fn get_inv_inertia_scale1(&self) -> f32 {
+        unsafe { PxContactSet_getInvInertiaScale1(&self.obj) }
+    }
+
+    /// Set the inverse inertia scale of the second actor.
+    pub fn set_inv_inertia_scale1(&mut self, scale: f32) {
+        unsafe { PxContactSet_setInvInertiaScale1_mut(&mut self.obj, scale) }
+    }
+}
@@ -1,9 +1,18 @@
 #![warn(clippy::all)]
 
 use crate::{
+    controller_callbacks::{
+        ControllerBehavior, ControllerBehaviorRaw, ControllerHitReport, ControllerHitReportRaw,
+    },
     material::Material,
     math::{PxExtendedVec3, PxVec3},
+    obstacle_context::{ObstacleContext, ObstacleHandle},
     owner::Owner,
+    rigid_actor::RigidActor,
+    rigid_dynamic::RigidDynamic,
+    scene::HitFlags,
+    scene_query::{FilterCallback, PostFilterHit, QueryFilter, QueryFlags, QueryHitType},
+    shape::CollisionLayers,
     traits::{Class, UserData},
 };
 
@@ -13,6 +22,8 @@ use thiserror::Error;
 
 #[rustfmt::skip]
 use physx_sys::{
+    destroy_controller_behavior_callback,
+    destroy_controller_hit_report,
     PxBoxControllerDesc_delete,
     PxBoxControllerDesc_isValid,
     PxBoxControllerDesc_new_alloc,
@@ -32,18 +43,261 @@ use physx_sys::{
     PxCapsuleController_setHeight_mut,
     PxCapsuleController_setRadius_mut,
     PxController,
+    PxControllerDesc_isValid,
+    PxControllerFilters_new,
+    PxControllerState,
+    PxControllerStats,
+    PxController_getActor,
+    PxController_getBehaviorCallback,
+    PxController_getContactOffset,
+    PxController_getFootPosition,
+    PxController_getNonWalkableMode,
     PxController_getPosition,
+    PxController_getReportCallback,
+    PxController_getSlopeLimit,
+    PxController_getState,
+    PxController_getStats,
+    PxController_getStepOffset,
+    PxController_getUpDirection,
     PxController_getUserData,
+    PxController_invalidateCache_mut,
+    PxController_move_mut,
     PxController_release_mut,
+    PxController_resize_mut,
+    PxController_setContactOffset_mut,
+    PxController_setFootPosition_mut,
+    PxController_setNonWalkableMode_mut,
     PxController_setPosition_mut,
+    PxController_setSlopeLimit_mut,
+    PxController_setStepOffset_mut,
+    PxController_setUpDirection_mut,
     PxController_setUserData_mut,
+    PxFilterData,
+    PxRigidActor,
+    PxShape,
 };
 
 pub use physx_sys::PxCapsuleClimbingMode as CapsuleClimbingMode;
+pub use physx_sys::PxControllerNonWalkableMode as ControllerNonWalkableMode;
+
+#[rustfmt::skip]
+pub use physx_sys::{
+    PxControllerCollisionFlag as ControllerCollisionFlag,
+    PxControllerCollisionFlags as ControllerCollisionFlags,
+};
+
+/// Filtering of the shapes a controller collides with during [`Controller::move_by`],
+/// parametrized by the actor type of the scene.
+pub struct ControllerFilters<'a, A: RigidActor> {
+    /// Only shapes whose query filter data (see [`Shape::set_query_filter_data`](crate::shape::Shape::set_query_filter_data))
+    /// shares a layer with this will block the controller.  Empty means no filtering.
+    pub layers: CollisionLayers,
+    /// Which actors to collide with, and how filtering should be done.
+    pub query_flags: QueryFlags,
+    filter: Option<&'a mut dyn QueryFilter<A>>,
+    obstacles: Option<&'a ObstacleContext>,
+}
+
+impl<A: RigidActor> Default for ControllerFilters<'_, A> {
+    fn default() -> Self {
+        Self::new(CollisionLayers::empty())
+    }
+}
+
+impl<'a, A: RigidActor> ControllerFilters<'a, A> {
+    /// Collide with static and dynamic shapes sharing a layer with `layers`.
+    pub fn new(layers: CollisionLayers) -> Self {
+        Self {
+            layers,
+            query_flags: QueryFlags::Static | QueryFlags::Dynamic | QueryFlags::Prefilter,
+            filter: None,
+            obstacles: None,
+        }
+    }
+
+    /// Run `filter` on the shapes that pass the layer test.  Controllers gather the shapes
+    /// around them with overlap queries, so post-filters receive [`PostFilterHit::Overlap`] hits.
+    pub fn with_filter(mut self, filter: &'a mut impl QueryFilter<A>) -> Self {
+        self.filter = Some(filter);
+        self
+    }
+
+    /// Also collide with the obstacles of `obstacles`.
+    pub fn with_obstacles(mut self, obstacles: &'a ObstacleContext) -> Self {
+        self.obstacles = Some(obstacles);
+        self
+    }
+}
+
+/// Forwards to the filter of [`ControllerFilters`], so a single callback shim is used
+/// whatever the type of the filter.
+struct DynQueryFilter<'a, 'b, A: RigidActor>(&'a mut (dyn QueryFilter<A> + 'b));
+
+impl<A: RigidActor> QueryFilter<A> for DynQueryFilter<'_, '_, A> {
+    fn pre_filter(
+        &mut self,
+        filter_data: &PxFilterData,
+        actor: &A,
+        shape: &A::Shape,
+        hit_flags: &mut HitFlags,
+    ) -> QueryHitType {
+        self.0.pre_filter(filter_data, actor, shape, hit_flags)
+    }
+
+    fn post_filter(
+        &mut self,
+        filter_data: &PxFilterData,
+        hit: PostFilterHit<'_, A>,
+    ) -> QueryHitType {
+        self.0.post_filter(filter_data, hit)
+    }
+}
+
+/// The state of a controller after its last move, parametrized by the actor type of the scene.
+pub struct ControllerState<'a, A: RigidActor> {
+    /// The displacement applied by the last move, from the motion of the touched shape or controller.
+    pub delta_xp: PxVec3,
+    /// The shape the controller is standing on.
+    pub touched_shape: Option<&'a A::Shape>,
+    /// The actor owning `touched_shape`.
+    pub touched_actor: Option<&'a A>,
+    /// The handle of the obstacle the controller is standing on.
+    pub touched_obstacle_handle: Option<ObstacleHandle>,
+    /// The collision flags returned by the last move.
+    pub collision_flags: ControllerCollisionFlags,
+    /// Whether the controller is standing on another controller.
+    pub stand_on_another_cct: bool,
+    /// Whether the controller is standing on an obstacle.
+    pub stand_on_obstacle: bool,
+    /// Whether the controller is moving up, or has been moved up by a jump.
+    pub is_moving_up: bool,
+}
+
+impl<A: RigidActor> ControllerState<'_, A> {
+    /// Whether the controller collided with something below it during its last move.
+    pub fn is_grounded(&self) -> bool {
+        self.collision_flags
+            .contains(ControllerCollisionFlags::CollisionDown)
+    }
+}
+
+/// Internal statistics of a controller.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct ControllerStats {
+    /// Number of iterations of the collide-and-slide loop in the last move.
+    pub nb_iterations: u16,
+    /// Number of times the cached geometry around the controller was fully rebuilt.
+    pub nb_full_updates: u16,
+    /// Number of times the cached geometry around the controller was partially updated.
+    pub nb_partial_updates: u16,
+    /// Number of triangles created by tessellation.
+    pub nb_tessellation: u16,
+}
+
+/// Settings shared by the capsule and box controller descriptors.
+pub trait ControllerDescriptor: Class<physx_sys::PxControllerDesc> {
+    /// Set the initial position of the center of the controller.
+    fn set_position(&mut self, position: impl Into<PxExtendedVec3>) -> &mut Self {
+        let position: PxExtendedVec3 = position.into();
+        unsafe {
+            (*self.as_mut_ptr()).position = position.into();
+        }
+        self
+    }
+
+    /// Set the up direction of the controller, the opposite of gravity.
+    fn set_up_direction(&mut self, up: &PxVec3) -> &mut Self {
+        unsafe {
+            (*self.as_mut_ptr()).upDirection = (*up).into();
+        }
+        self
+    }
+
+    /// Set the cosine of the steepest slope the controller can walk up.  Zero disables
+    /// the limit, which can not be enabled afterwards with [`Controller::set_slope_limit`].
+    fn set_slope_limit(&mut self, slope_limit: f32) -> &mut Self {
+        unsafe {
+            (*self.as_mut_ptr()).slopeLimit = slope_limit;
+        }
+        self
+    }
+
+    /// Set the height of the invisible walls created around non-walkable triangles,
+    /// zero for none.
+    fn set_invisible_wall_height(&mut self, height: f32) -> &mut Self {
+        unsafe {
+            (*self.as_mut_ptr()).invisibleWallHeight = height;
+        }
+        self
+    }
+
+    /// Set the maximum height of a jump, used to limit the invisible walls.
+    fn set_max_jump_height(&mut self, height: f32) -> &mut Self {
+        unsafe {
+            (*self.as_mut_ptr()).maxJumpHeight = height;
+        }
+        self
+    }
+
+    /// Set the skin width of the controller.
+    fn set_contact_offset(&mut self, offset: f32) -> &mut Self {
+        unsafe {
+            (*self.as_mut_ptr()).contactOffset = offset;
+        }
+        self
+    }
+
+    /// Set the maximum height of the obstacles the controller can step over.
+    fn set_step_offset(&mut self, offset: f32) -> &mut Self {
+        unsafe {
+            (*self.as_mut_ptr()).stepOffset = offset;
+        }
+        self
+    }
+
+    /// Set the density of the kinematic actor of the controller.
+    fn set_density(&mut self, density: f32) -> &mut Self {
+        unsafe {
+            (*self.as_mut_ptr()).density = density;
+        }
+        self
+    }
+
+    /// Set the scale of the kinematic actor compared to the controller, usually a bit below 1.
+    fn set_scale_coeff(&mut self, scale: f32) -> &mut Self {
+        unsafe {
+            (*self.as_mut_ptr()).scaleCoeff = scale;
+        }
+        self
+    }
+
+    /// Set how much larger than the motion the cached volume around the controller is.
+    fn set_volume_growth(&mut self, growth: f32) -> &mut Self {
+        unsafe {
+            (*self.as_mut_ptr()).volumeGrowth = growth;
+        }
+        self
+    }
+
+    /// Set how the controller behaves on non-walkable slopes.
+    fn set_non_walkable_mode(&mut self, mode: ControllerNonWalkableMode) -> &mut Self {
+        unsafe {
+            (*self.as_mut_ptr()).nonWalkableMode = mode;
+        }
+        self
+    }
+
+    /// Whether the settings are valid to create a controller.
+    fn is_valid(&self) -> bool {
+        unsafe { PxControllerDesc_isValid(self.as_ptr()) }
+    }
+}
 
 pub trait Controller: Class<PxController> + Sized {
     type UserData;
-    type Descriptor: Class<physx_sys::PxControllerDesc>;
+    type Descriptor: ControllerDescriptor;
+    /// The actor type of the scene the controller manager was created from.
+    type ActorMap: RigidActor;
 
     /// Retrieve the user data from the controller.
     // Due to the size trick employed and the API decision to expose this userData via method calls,
@@ -94,15 +348,198 @@ pub trait Controller: Class<PxController> + Sized {
     fn get_position(&self) -> PxVec3 {
         unsafe { (*PxController_getPosition(self.as_ptr())).into() }
     }
+
+    /// Set the position of the bottom of the controller.
+    fn set_foot_position(&mut self, position: impl Into<PxExtendedVec3>) -> bool {
+        unsafe {
+            let position: PxExtendedVec3 = position.into();
+            PxController_setFootPosition_mut(self.as_mut_ptr(), position.as_ptr())
+        }
+    }
+
+    /// Get the position of the bottom of the controller.
+    fn get_foot_position(&self) -> PxExtendedVec3 {
+        unsafe { PxController_getFootPosition(self.as_ptr()).into() }
+    }
+
+    /// Set the maximum height of the obstacles the controller can step over.
+    fn set_step_offset(&mut self, offset: f32) {
+        unsafe { PxController_setStepOffset_mut(self.as_mut_ptr(), offset) }
+    }
+
+    /// Get the maximum height of the obstacles the controller can step over.
+    fn get_step_offset(&self) -> f32 {
+        unsafe { PxController_getStepOffset(self.as_ptr()) }
+    }
+
+    /// Set the cosine of the steepest slope the controller can walk up.  Ignored if the
+    /// controller was created without a slope limit.
+    fn set_slope_limit(&mut self, slope_limit: f32) {
+        unsafe { PxController_setSlopeLimit_mut(self.as_mut_ptr(), slope_limit) }
+    }
+
+    /// Get the cosine of the steepest slope the controller can walk up.
+    fn get_slope_limit(&self) -> f32 {
+        unsafe { PxController_getSlopeLimit(self.as_ptr()) }
+    }
+
+    /// Set the up direction of the controller, the opposite of gravity.
+    fn set_up_direction(&mut self, up: &PxVec3) {
+        unsafe { PxController_setUpDirection_mut(self.as_mut_ptr(), up.as_ptr()) }
+    }
+
+    /// Get the up direction of the controller.
+    fn get_up_direction(&self) -> PxVec3 {
+        unsafe { PxController_getUpDirection(self.as_ptr()).into() }
+    }
+
+    /// Set how the controller behaves on non-walkable slopes.
+    fn set_non_walkable_mode(&mut self, mode: ControllerNonWalkableMode) {
+        unsafe { PxController_setNonWalkableMode_mut(self.as_mut_ptr(), mode) }
+    }
+
+    /// Get how the controller behaves on non-walkable slopes.
+    fn get_non_walkable_mode(&self) -> ControllerNonWalkableMode {
+        unsafe { PxController_getNonWalkableMode(self.as_ptr()) }
+    }
+
+    /// Set the skin width of the controller.
+    fn set_contact_offset(&mut self, offset: f32) {
+        unsafe { PxController_setContactOffset_mut(self.as_mut_ptr(), offset) }
+    }
+
+    /// Get the skin width of the controller.
+    fn get_contact_offset(&self) -> f32 {
+        unsafe { PxController_getContactOffset(self.as_ptr()) }
+    }
+
+    /// Change the height of the controller while keeping its bottom in place, for example
+    /// to crouch.  Does not check whether there is room for the new height.
+    fn resize(&mut self, height: f32) {
+        unsafe { PxController_resize_mut(self.as_mut_ptr(), height) }
+    }
+
+    /// Flush the cached geometry around the controller, after shapes were moved or removed
+    /// in a way the controller can not notice.
+    fn invalidate_cache(&mut self) {
+        unsafe { PxController_invalidateCache_mut(self.as_mut_ptr()) }
+    }
+
+    /// Get the kinematic actor moved by the controller, for example to attach trigger shapes
+    /// to it.  PhysX creates the actor and its shape without user data, so they can only be
+    /// accessed as types with no user data.  The actor is owned by the controller and must
+    /// not be released.
+    fn get_actor<D>(&self) -> &D
+    where
+        D: RigidDynamic + UserData<UserData = ()>,
+        D::Shape: UserData<UserData = ()>,
+    {
+        unsafe { &*(PxController_getActor(self.as_ptr()) as *const D) }
+    }
+
+    /// Get the kinematic actor moved by the controller, see [`Controller::get_actor`].
+    fn get_actor_mut<D>(&mut self) -> &mut D
+    where
+        D: RigidDynamic + UserData<UserData = ()>,
+        D::Shape: UserData<UserData = ()>,
+    {
+        unsafe { &mut *(PxController_getActor(self.as_ptr()) as *mut D) }
+    }
+
+    /// Move the controller by `displacement`, sliding along and climbing over the shapes
+    /// that pass `filters` and the obstacles added to them.  The move stops when the remaining
+    /// displacement is shorter than `min_dist`.  `elapsed_time` is the time since the last move,
+    /// used by the controller to follow the motion of what it stands on.  Returns which sides
+    /// of the controller collided.
+    fn move_by(
+        &mut self,
+        displacement: &PxVec3,
+        min_dist: f32,
+        elapsed_time: f32,
+        filters: &mut ControllerFilters<'_, Self::ActorMap>,
+    ) -> ControllerCollisionFlags {
+        let data = PxFilterData {
+            word0: filters.layers.bits(),
+            word1: 0,
+            word2: 0,
+            word3: 0,
+        };
+        let mut query_flags = filters.query_flags;
+        let mut filter = filters.filter.as_deref_mut().map(DynQueryFilter);
+        let mut callback = filter.as_mut().map(|filter| {
+            if !query_flags.intersects(QueryFlags::Prefilter | QueryFlags::Postfilter) {
+                query_flags |= QueryFlags::Prefilter;
+            }
+            FilterCallback::overlap(filter)
+        });
+        unsafe {
+            let mut raw_filters = PxControllerFilters_new(
+                &data,
+                callback
+                    .as_mut()
+                    .map_or(std::ptr::null_mut(), |callback| callback.as_mut_ptr()),
+                std::ptr::null_mut(),
+            );
+            raw_filters.mFilterFlags = query_flags;
+            PxController_move_mut(
+                self.as_mut_ptr(),
+                displacement.as_ptr(),
+                min_dist,
+                elapsed_time,
+                &raw_filters,
+                filters
+                    .obstacles
+                    .map_or(std::ptr::null(), |obstacles| obstacles.as_ptr()),
+            )
+        }
+    }
+
+    /// Get the state of the controller after its last move, for example to check whether it is grounded.
+    fn get_state(&self) -> ControllerState<'_, Self::ActorMap> {
+        unsafe {
+            let mut state: PxControllerState = std::mem::zeroed();
+            PxController_getState(self.as_ptr(), &mut state);
+            ControllerState {
+                delta_xp: state.deltaXP.into(),
+                touched_shape: (state.touchedShape as *const PxShape
+                    as *const <Self::ActorMap as RigidActor>::Shape)
+                    .as_ref(),
+                touched_actor: (state.touchedActor as *const PxRigidActor as *const Self::ActorMap)
+                    .as_ref(),
+                touched_obstacle_handle: ObstacleHandle::from_raw(state.touchedObstacleHandle),
+                collision_flags: ControllerCollisionFlags::from_bits_truncate(
+                    state.collisionFlags as u8,
+                ),
+                stand_on_another_cct: state.standOnAnotherCCT,
+                stand_on_obstacle: state.standOnObstacle,
+                is_moving_up: state.isMovingUp,
+            }
+        }
+    }
+
+    /// Get the internal statistics of the controller.
+    fn get_stats(&self) -> ControllerStats {
+        unsafe {
+            let mut stats: PxControllerStats = std::mem::zeroed();
+            PxController_getStats(self.as_ptr(), &mut stats);
+            ControllerStats {
+                nb_iterations: stats.nbIterations,
+                nb_full_updates: stats.nbFullUpdates,
+                nb_partial_updates: stats.nbPartialUpdates,
+                nb_tessellation: stats.nbTessellation,
+            }
+        }
+    }
 }
 
 #[repr(transparent)]
-pub struct PxCapsuleController<U> {
+pub struct PxCapsuleController<U, A> {
     obj: physx_sys::PxCapsuleController,
     phantom_user_data: PhantomData<U>,
+    phantom_actor_map: PhantomData<*const A>,
 }
 
-unsafe impl<T, U> Class<T> for PxCapsuleController<U>
+unsafe impl<T, U, A> Class<T> for PxCapsuleController<U, A>
 where
     physx_sys::PxCapsuleController: Class<T>,
 {
@@ -115,7 +552,7 @@ where
     }
 }
 
-impl<U> Drop for PxCapsuleController<U> {
+impl<U, A> Drop for PxCapsuleController<U, A> {
     fn drop(&mut self) {
         unsafe {
             if size_of::<U>() > size_of::<*mut c_void>() {
@@ -125,20 +562,25 @@ impl<U> Drop for PxCapsuleController<U> {
                     (&mut PxController_getUserData(self.as_ptr())) as *mut *mut c_void as *mut U,
                 );
             };
-            PxController_release_mut(self.as_mut_ptr())
+            let report = PxController_getReportCallback(self.as_ptr());
+            let behavior = PxController_getBehaviorCallback(self.as_ptr());
+            PxController_release_mut(self.as_mut_ptr());
+            destroy_controller_hit_report(report);
+            destroy_controller_behavior_callback(behavior);
         }
     }
 }
 
-unsafe impl<U: Send> Send for PxCapsuleController<U> {}
-unsafe impl<U: Sync> Sync for PxCapsuleController<U> {}
+unsafe impl<U: Send, A> Send for PxCapsuleController<U, A> {}
+unsafe impl<U: Sync, A> Sync for PxCapsuleController<U, A> {}
 
-impl<U> Controller for PxCapsuleController<U> {
+impl<U, A: RigidActor> Controller for PxCapsuleController<U, A> {
     type UserData = U;
-    type Descriptor = PxCapsuleControllerDesc<U>;
+    type Descriptor = PxCapsuleControllerDesc<U, A>;
+    type ActorMap = A;
 }
 
-impl<U> CapsuleController for PxCapsuleController<U> {}
+impl<U, A: RigidActor> CapsuleController for PxCapsuleController<U, A> {}
 
 pub trait CapsuleController: Class<physx_sys::PxCapsuleController> + Controller {
     /// Get the climbing mode of the capsule controller.
@@ -172,14 +614,16 @@ pub trait CapsuleController: Class<physx_sys::PxCapsuleController> + Controller
     }
 }
 
-/// A new type wrapper for `physx_sys::PxCapsuleControllerDesc`, parametrized by the user data type.
+/// A new type wrapper for `physx_sys::PxCapsuleControllerDesc`, parametrized by the user data type
+/// and the actor type of the scene.
 #[repr(transparent)]
-pub struct PxCapsuleControllerDesc<U> {
+pub struct PxCapsuleControllerDesc<U, A> {
     pub(crate) obj: physx_sys::PxCapsuleControllerDesc,
     phantom_user_data: PhantomData<U>,
+    phantom_actor_map: PhantomData<*const A>,
 }
 
-unsafe impl<T, U> Class<T> for PxCapsuleControllerDesc<U>
+unsafe impl<T, U, A> Class<T> for PxCapsuleControllerDesc<U, A>
 where
     physx_sys::PxCapsuleControllerDesc: Class<T>,
 {
@@ -192,7 +636,7 @@ where
     }
 }
 
-impl<U> PxCapsuleControllerDesc<U> {
+impl<U, A: RigidActor> PxCapsuleControllerDesc<U, A> {
     /// Create a new capsule controller descriptor.
     pub fn new<M: Material>(
         height: f32,
@@ -220,6 +664,30 @@ impl<U> PxCapsuleControllerDesc<U> {
         }
     }
 
+    /// Set the report of what the controller hits while it moves, replacing any previous one.
+    pub fn set_hit_report(
+        &mut self,
+        report: impl ControllerHitReport<PxCapsuleController<U, A>>,
+    ) -> &mut Self {
+        unsafe {
+            destroy_controller_hit_report(self.obj.reportCallback);
+        }
+        self.obj.reportCallback = report.into_raw_report();
+        self
+    }
+
+    /// Set the behavior of the controller on what it touches, replacing any previous one.
+    pub fn set_behavior(
+        &mut self,
+        behavior: impl ControllerBehavior<PxCapsuleController<U, A>>,
+    ) -> &mut Self {
+        unsafe {
+            destroy_controller_behavior_callback(self.obj.behaviorCallback);
+        }
+        self.obj.behaviorCallback = behavior.into_raw_callback();
+        self
+    }
+
     /// # Safety
     ///
     /// Owner's own the pointer they wrap, using the pointer after dropping the Owner,
@@ -233,7 +701,7 @@ impl<U> PxCapsuleControllerDesc<U> {
     }
 }
 
-unsafe impl<U> UserData for PxCapsuleControllerDesc<U> {
+unsafe impl<U, A> UserData for PxCapsuleControllerDesc<U, A> {
     type UserData = U;
 
     fn user_data_ptr(&self) -> &*mut c_void {
@@ -245,22 +713,27 @@ unsafe impl<U> UserData for PxCapsuleControllerDesc<U> {
     }
 }
 
-impl<U> Drop for PxCapsuleControllerDesc<U> {
+impl<U, A> ControllerDescriptor for PxCapsuleControllerDesc<U, A> {}
+
+impl<U, A> Drop for PxCapsuleControllerDesc<U, A> {
     fn drop(&mut self) {
         unsafe {
             drop_in_place(UserData::get_user_data_mut(self) as *mut _);
+            destroy_controller_hit_report(self.obj.reportCallback);
+            destroy_controller_behavior_callback(self.obj.behaviorCallback);
             PxCapsuleControllerDesc_delete(self.as_mut_ptr());
         }
     }
 }
 
 #[repr(transparent)]
-pub struct PxBoxController<U> {
+pub struct PxBoxController<U, A> {
     obj: physx_sys::PxBoxController,
     phantom_user_data: PhantomData<U>,
+    phantom_actor_map: PhantomData<*const A>,
 }
 
-unsafe impl<T, U> Class<T> for PxBoxController<U>
+unsafe impl<T, U, A> Class<T> for PxBoxController<U, A>
 where
     physx_sys::PxBoxController: Class<T>,
 {
@@ -273,7 +746,7 @@ where
     }
 }
 
-impl<U> Drop for PxBoxController<U> {
+impl<U, A> Drop for PxBoxController<U, A> {
     fn drop(&mut self) {
         unsafe {
             if size_of::<U>() > size_of::<*mut c_void>() {
@@ -283,20 +756,25 @@ impl<U> Drop for PxBoxController<U> {
                     (&mut PxController_getUserData(self.as_ptr())) as *mut *mut c_void as *mut U,
                 );
             };
-            PxController_release_mut(self.as_mut_ptr())
+            let report = PxController_getReportCallback(self.as_ptr());
+            let behavior = PxController_getBehaviorCallback(self.as_ptr());
+            PxController_release_mut(self.as_mut_ptr());
+            destroy_controller_hit_report(report);
+            destroy_controller_behavior_callback(behavior);
         }
     }
 }
 
-unsafe impl<U: Send> Send for PxBoxController<U> {}
-unsafe impl<U: Sync> Sync for PxBoxController<U> {}
+unsafe impl<U: Send, A> Send for PxBoxController<U, A> {}
+unsafe impl<U: Sync, A> Sync for PxBoxController<U, A> {}
 
-impl<U> Controller for PxBoxController<U> {
+impl<U, A: RigidActor> Controller for PxBoxController<U, A> {
     type UserData = U;
-    type Descriptor = PxBoxControllerDesc<U>;
+    type Descriptor = PxBoxControllerDesc<U, A>;
+    type ActorMap = A;
 }
 
-impl<U> BoxController for PxBoxController<U> {}
+impl<U, A: RigidActor> BoxController for PxBoxController<U, A> {}
 
 pub trait BoxController: Class<physx_sys::PxBoxController> + Controller {
     /// Get the half forward extent.
@@ -330,14 +808,16 @@ pub trait BoxController: Class<physx_sys::PxBoxController> + Controller {
     }
 }
 
-/// A new type wrapper for `physx_sys::PxBoxControllerDesc`, parametrized by the user data type.
+/// A new type wrapper for `physx_sys::PxBoxControllerDesc`, parametrized by the user data type
+/// and the actor type of the scene.
 #[repr(transparent)]
-pub struct PxBoxControllerDesc<U> {
+pub struct PxBoxControllerDesc<U, A> {
     obj: physx_sys::PxBoxControllerDesc,
     phantom_user_data: PhantomData<U>,
+    phantom_actor_map: PhantomData<*const A>,
 }
 
-unsafe impl<T, U> Class<T> for PxBoxControllerDesc<U>
+unsafe impl<T, U, A> Class<T> for PxBoxControllerDesc<U, A>
 where
     physx_sys::PxBoxControllerDesc: Class<T>,
 {
@@ -350,7 +830,7 @@ where
     }
 }
 
-impl<U> PxBoxControllerDesc<U> {
+impl<U, A: RigidActor> PxBoxControllerDesc<U, A> {
     /// Create a new box controller descriptor.
     pub fn new<M: Material>(
         half_height: f32,
@@ -380,6 +860,30 @@ impl<U> PxBoxControllerDesc<U> {
         }
     }
 
+    /// Set the report of what the controller hits while it moves, replacing any previous one.
+    pub fn set_hit_report(
+        &mut self,
+        report: impl ControllerHitReport<PxBoxController<U, A>>,
+    ) -> &mut Self {
+        unsafe {
+            destroy_controller_hit_report(self.obj.reportCallback);
+        }
+        self.obj.reportCallback = report.into_raw_report();
+        self
+    }
+
+    /// Set the behavior of the controller on what it touches, replacing any previous one.
+    pub fn set_behavior(
+        &mut self,
+        behavior: impl ControllerBehavior<PxBoxController<U, A>>,
+    ) -> &mut Self {
+        unsafe {
+            destroy_controller_behavior_callback(self.obj.behaviorCallback);
+        }
+        self.obj.behaviorCallback = behavior.into_raw_callback();
+        self
+    }
+
     /// # Safety
     ///
     /// Owner's own the pointer they wrap, using the pointer after dropping the Owner,
@@ -393,7 +897,7 @@ impl<U> PxBoxControllerDesc<U> {
     }
 }
 
-unsafe impl<U> UserData for PxBoxControllerDesc<U> {
+unsafe impl<U, A> UserData for PxBoxControllerDesc<U, A> {
     type UserData = U;
 
     fn user_data_ptr(&self) -> &*mut c_void {
@@ -405,10 +909,14 @@ unsafe impl<U> UserData for PxBoxControllerDesc<U> {
     }
 }
 
-impl<U> Drop for PxBoxControllerDesc<U> {
+impl<U, A> ControllerDescriptor for PxBoxControllerDesc<U, A> {}
+
+impl<U, A> Drop for PxBoxControllerDesc<U, A> {
     fn drop(&mut self) {
         unsafe {
             drop_in_place(UserData::get_user_data_mut(self) as *mut _);
+            destroy_controller_hit_report(self.obj.reportCallback);
+            destroy_controller_behavior_callback(self.obj.behaviorCallback);
             PxBoxControllerDesc_delete(self.as_mut_ptr())
         }
     }
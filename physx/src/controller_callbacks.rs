@@ -0,0 +1,271 @@
+use crate::{
+    controller::Controller,
+    math::{PxExtendedVec3, PxVec3},
+    rigid_actor::RigidActor,
+};
+
+use std::ffi::c_void;
+
+#[rustfmt::skip]
+use physx_sys::{
+    create_controller_behavior_callback,
+    create_controller_hit_report,
+    ControllerBehaviorCallbacks,
+    ControllerHitReportCallbacks,
+    PxActor,
+    PxController,
+    PxControllerBehaviorCallback,
+    PxControllerObstacleHit,
+    PxControllerShapeHit,
+    PxControllersHit,
+    PxObstacle,
+    PxShape,
+    PxUserControllerHitReport,
+};
+
+#[rustfmt::skip]
+pub use physx_sys::{
+    PxControllerBehaviorFlag as ControllerBehaviorFlag,
+    PxControllerBehaviorFlags as ControllerBehaviorFlags,
+};
+
+/// A shape hit by a controller while it moves.  Parametrized by the controller type
+/// of the controller manager, whose actor type is the one of the scene.
+pub struct ControllerShapeHit<'a, C: Controller> {
+    /// The controller that moved.
+    pub controller: &'a C,
+    /// The shape that was hit.
+    pub shape: &'a <C::ActorMap as RigidActor>::Shape,
+    /// The actor owning `shape`.
+    pub actor: &'a C::ActorMap,
+    /// World space position of the contact.
+    pub world_pos: PxExtendedVec3,
+    /// World space normal of the contact.
+    pub world_normal: PxVec3,
+    /// The direction the controller was moving in.
+    pub dir: PxVec3,
+    /// The length of the motion.
+    pub length: f32,
+    /// The index of the triangle that was hit for mesh and height field shapes.
+    pub triangle_index: u32,
+}
+
+/// Another controller hit by a controller while it moves.
+pub struct ControllersHit<'a, C: Controller> {
+    /// The controller that moved.
+    pub controller: &'a C,
+    /// The controller that was hit.
+    pub other: &'a C,
+    /// World space position of the contact.
+    pub world_pos: PxExtendedVec3,
+    /// World space normal of the contact.
+    pub world_normal: PxVec3,
+    /// The direction the controller was moving in.
+    pub dir: PxVec3,
+    /// The length of the motion.
+    pub length: f32,
+}
+
+/// An obstacle hit by a controller while it moves.
+pub struct ControllerObstacleHit<'a, C: Controller> {
+    /// The controller that moved.
+    pub controller: &'a C,
+    /// The user data of the obstacle that was hit.
+    pub user_data: usize,
+    /// World space position of the contact.
+    pub world_pos: PxExtendedVec3,
+    /// World space normal of the contact.
+    pub world_normal: PxVec3,
+    /// The direction the controller was moving in.
+    pub dir: PxVec3,
+    /// The length of the motion.
+    pub length: f32,
+}
+
+/// Reports what a controller hits while it moves, for example to push the dynamic actors
+/// it walks into.  Parametrized by the controller type of the controller manager.
+///
+/// The callbacks are called from [`Controller::move_by`].
+#[allow(unused_variables)]
+pub trait ControllerHitReport<C: Controller>: Send + 'static {
+    /// Called when the controller hits a shape.
+    fn on_shape_hit(&mut self, hit: &ControllerShapeHit<'_, C>) {}
+
+    /// Called when the controller hits another controller.
+    fn on_controller_hit(&mut self, hit: &ControllersHit<'_, C>) {}
+
+    /// Called when the controller hits an obstacle.
+    fn on_obstacle_hit(&mut self, hit: &ControllerObstacleHit<'_, C>) {}
+}
+
+impl<T, C> ControllerHitReportRaw<C> for T
+where
+    T: ControllerHitReport<C>,
+    C: Controller,
+{
+}
+
+pub(crate) trait ControllerHitReportRaw<C: Controller>:
+    ControllerHitReport<C> + Sized
+{
+    unsafe extern "C" fn shape_hit_callback(this: *mut c_void, hit: *const PxControllerShapeHit) {
+        unsafe {
+            let hit = &*hit;
+            Self::on_shape_hit(
+                &mut *(this as *mut Self),
+                &ControllerShapeHit {
+                    controller: &*(hit.controller as *const C),
+                    shape: &*(hit.shape as *const <C::ActorMap as RigidActor>::Shape),
+                    actor: &*(hit.actor as *const C::ActorMap),
+                    world_pos: hit.worldPos.into(),
+                    world_normal: hit.worldNormal.into(),
+                    dir: hit.dir.into(),
+                    length: hit.length,
+                    triangle_index: hit.triangleIndex,
+                },
+            )
+        }
+    }
+
+    unsafe extern "C" fn controller_hit_callback(this: *mut c_void, hit: *const PxControllersHit) {
+        unsafe {
+            let hit = &*hit;
+            Self::on_controller_hit(
+                &mut *(this as *mut Self),
+                &ControllersHit {
+                    controller: &*(hit.controller as *const C),
+                    other: &*(hit.other as *const C),
+                    world_pos: hit.worldPos.into(),
+                    world_normal: hit.worldNormal.into(),
+                    dir: hit.dir.into(),
+                    length: hit.length,
+                },
+            )
+        }
+    }
+
+    unsafe extern "C" fn obstacle_hit_callback(
+        this: *mut c_void,
+        hit: *const PxControllerObstacleHit,
+    ) {
+        unsafe {
+            let hit = &*hit;
+            Self::on_obstacle_hit(
+                &mut *(this as *mut Self),
+                &ControllerObstacleHit {
+                    controller: &*(hit.controller as *const C),
+                    user_data: hit.userData as usize,
+                    world_pos: hit.worldPos.into(),
+                    world_normal: hit.worldNormal.into(),
+                    dir: hit.dir.into(),
+                    length: hit.length,
+                },
+            )
+        }
+    }
+
+    unsafe extern "C" fn drop_callback(this: *mut c_void) {
+        unsafe { drop(Box::from_raw(this as *mut Self)) }
+    }
+
+    /// Box the report into a PxUserControllerHitReport, destroyed with `destroy_controller_hit_report`.
+    fn into_raw_report(self) -> *mut PxUserControllerHitReport {
+        let callbacks = ControllerHitReportCallbacks {
+            on_shape_hit: Self::shape_hit_callback,
+            on_controller_hit: Self::controller_hit_callback,
+            on_obstacle_hit: Self::obstacle_hit_callback,
+            drop_user_data: Self::drop_callback,
+            user_data: Box::into_raw(Box::new(self)) as *mut c_void,
+        };
+        unsafe { create_controller_hit_report(&callbacks) }
+    }
+}
+
+/// Customizes how a controller behaves on what it touches, for example to let it ride on
+/// moving platforms and elevators, or slide off other characters.  Parametrized by the
+/// controller type of the controller manager.
+///
+/// All methods return no flags by default, which is also the behavior of controllers
+/// without a behavior callback.
+#[allow(unused_variables)]
+pub trait ControllerBehavior<C: Controller>: Send + 'static {
+    /// The behavior of the controller on a shape it touches.
+    fn shape_behavior_flags(
+        &mut self,
+        shape: &<C::ActorMap as RigidActor>::Shape,
+        actor: &C::ActorMap,
+    ) -> ControllerBehaviorFlags {
+        ControllerBehaviorFlags::empty()
+    }
+
+    /// The behavior of the controller on another controller it touches.
+    /// `ControllerBehaviorFlag::CctCanRideOnObject` is not supported for controllers.
+    fn controller_behavior_flags(&mut self, controller: &C) -> ControllerBehaviorFlags {
+        ControllerBehaviorFlags::empty()
+    }
+
+    /// The behavior of the controller on an obstacle it touches, identified by its user data.
+    fn obstacle_behavior_flags(&mut self, user_data: usize) -> ControllerBehaviorFlags {
+        ControllerBehaviorFlags::empty()
+    }
+}
+
+impl<T, C> ControllerBehaviorRaw<C> for T
+where
+    T: ControllerBehavior<C>,
+    C: Controller,
+{
+}
+
+pub(crate) trait ControllerBehaviorRaw<C: Controller>:
+    ControllerBehavior<C> + Sized
+{
+    unsafe extern "C" fn shape_callback(
+        this: *mut c_void,
+        shape: *const PxShape,
+        actor: *const PxActor,
+    ) -> ControllerBehaviorFlags {
+        unsafe {
+            Self::shape_behavior_flags(
+                &mut *(this as *mut Self),
+                &*(shape as *const <C::ActorMap as RigidActor>::Shape),
+                &*(actor as *const C::ActorMap),
+            )
+        }
+    }
+
+    unsafe extern "C" fn controller_callback(
+        this: *mut c_void,
+        controller: *const PxController,
+    ) -> ControllerBehaviorFlags {
+        unsafe {
+            Self::controller_behavior_flags(&mut *(this as *mut Self), &*(controller as *const C))
+        }
+    }
+
+    unsafe extern "C" fn obstacle_callback(
+        this: *mut c_void,
+        obstacle: *const PxObstacle,
+    ) -> ControllerBehaviorFlags {
+        unsafe {
+            Self::obstacle_behavior_flags(&mut *(this as *mut Self), (*obstacle).mUserData as usize)
+        }
+    }
+
+    unsafe extern "C" fn drop_callback(this: *mut c_void) {
+        unsafe { drop(Box::from_raw(this as *mut Self)) }
+    }
+
+    /// Box the behavior into a PxControllerBehaviorCallback, destroyed with
+    /// `destroy_controller_behavior_callback`.
+    fn into_raw_callback(self) -> *mut PxControllerBehaviorCallback {
+        let callbacks = ControllerBehaviorCallbacks {
+            get_shape_behavior_flags: Self::shape_callback,
+            get_controller_behavior_flags: Self::controller_callback,
+            get_obstacle_behavior_flags: Self::obstacle_callback,
+            drop_user_data: Self::drop_callback,
+            user_data: Box::into_raw(Box::new(self)) as *mut c_void,
+        };
+        unsafe { create_controller_behavior_callback(&callbacks) }
+    }
+}
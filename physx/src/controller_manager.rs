@@ -1,14 +1,34 @@
-use crate::{controller::Controller, owner::Owner, traits::Class};
+use crate::{
+    controller::Controller, math::PxVec3, obstacle_context::ObstacleContext, owner::Owner,
+    render_buffer::RenderBuffer, traits::Class,
+};
 
 use std::{marker::PhantomData, ptr::drop_in_place};
 
 #[rustfmt::skip]
 use physx_sys::{
+    PxControllerManager_computeInteractions_mut,
     PxControllerManager_createController_mut,
+    PxControllerManager_createObstacleContext_mut,
     PxControllerManager_getController_mut,
     PxControllerManager_getNbControllers,
+    PxControllerManager_getNbObstacleContexts,
+    PxControllerManager_getObstacleContext_mut,
+    PxControllerManager_getRenderBuffer_mut,
     PxControllerManager_purgeControllers_mut,
     PxControllerManager_release_mut,
+    PxControllerManager_setDebugRenderingFlags_mut,
+    PxControllerManager_setOverlapRecoveryModule_mut,
+    PxControllerManager_setPreciseSweeps_mut,
+    PxControllerManager_setPreventVerticalSlidingAgainstCeiling_mut,
+    PxControllerManager_setTessellation_mut,
+    PxControllerManager_shiftOrigin_mut,
+};
+
+#[rustfmt::skip]
+pub use physx_sys::{
+    PxControllerDebugRenderFlag as ControllerDebugRenderFlag,
+    PxControllerDebugRenderFlags as ControllerDebugRenderFlags,
 };
 
 #[repr(transparent)]
@@ -91,19 +111,131 @@ pub trait ControllerManager: Class<physx_sys::PxControllerManager> + Sized {
         }
         vec
     }
-}
 
-unsafe impl<C: Controller + Send> Send for PxControllerManager<C> {}
-unsafe impl<C: Controller + Sync> Sync for PxControllerManager<C> {}
+    /// Create a context for obstacles that are not actors of the scene.  The context is
+    /// owned by the controller manager and released with it, its index is the number of
+    /// contexts created before it.
+    fn create_obstacle_context(&mut self) -> Option<&mut ObstacleContext> {
+        unsafe {
+            (PxControllerManager_createObstacleContext_mut(self.as_mut_ptr())
+                as *mut ObstacleContext)
+                .as_mut()
+        }
+    }
 
-impl<C: Controller> Drop for PxControllerManager<C> {
-    fn drop(&mut self) {
+    /// Get the number of obstacle contexts created by the controller manager.
+    fn get_nb_obstacle_contexts(&self) -> u32 {
+        unsafe { PxControllerManager_getNbObstacleContexts(self.as_ptr()) }
+    }
+
+    /// Get an obstacle context by index.  Returns `None` if the index is out of range.
+    fn get_obstacle_context(&mut self, idx: u32) -> Option<&mut ObstacleContext> {
+        unsafe {
+            if idx < self.get_nb_obstacle_contexts() {
+                (PxControllerManager_getObstacleContext_mut(self.as_mut_ptr(), idx)
+                    as *mut ObstacleContext)
+                    .as_mut()
+            } else {
+                None
+            }
+        }
+    }
+
+    /// Get a controller and an obstacle context by index at the same time, to pass the
+    /// obstacles to [`ControllerFilters::with_obstacles`](crate::controller::ControllerFilters::with_obstacles)
+    /// when moving the controller.  Returns `None` if either index is out of range.
+    fn get_controller_and_obstacle_context(
+        &mut self,
+        controller_idx: u32,
+        obstacle_context_idx: u32,
+    ) -> Option<(&mut Self::Controller, &ObstacleContext)> {
+        let obstacles = self.get_obstacle_context(obstacle_context_idx)? as *const ObstacleContext;
+        let controller = self.get_controller(controller_idx)?;
+        // Safety: the controller and the obstacle context are distinct objects.
+        Some((controller, unsafe { &*obstacles }))
+    }
+
+    /// Release all the controllers, dropping their user data and callbacks.
+    fn purge_controllers(&mut self) {
         unsafe {
             for controller in self.get_controllers() {
                 drop_in_place(controller as *mut _);
             }
             PxControllerManager_purgeControllers_mut(self.as_mut_ptr());
-            PxControllerManager_release_mut(self.as_mut_ptr());
         }
     }
+
+    /// Compute the interactions between the controllers, so that overlapping controllers
+    /// push each other apart.  Call it once per frame, before moving the controllers.
+    /// Every pair of controllers interacts, there is no filtering.
+    fn compute_interactions(&mut self, elapsed_time: f32) {
+        unsafe {
+            PxControllerManager_computeInteractions_mut(
+                self.as_mut_ptr(),
+                elapsed_time,
+                std::ptr::null_mut(),
+            )
+        }
+    }
+
+    /// Enable or disable the tessellation of large static triangles and boxes touched by
+    /// the controllers, to work around precision issues.  Triangles with edges longer than
+    /// `max_edge_length` are tessellated.  Disabled by default.
+    fn set_tessellation(&mut self, enabled: bool, max_edge_length: f32) {
+        unsafe {
+            PxControllerManager_setTessellation_mut(self.as_mut_ptr(), enabled, max_edge_length)
+        }
+    }
+
+    /// Enable or disable the module that pushes controllers out of the geometry they
+    /// overlap.  Enabled by default.
+    fn set_overlap_recovery_module(&mut self, enabled: bool) {
+        unsafe { PxControllerManager_setOverlapRecoveryModule_mut(self.as_mut_ptr(), enabled) }
+    }
+
+    /// Enable or disable precise sweeps, which are slower but more accurate.
+    /// Enabled by default.
+    fn set_precise_sweeps(&mut self, enabled: bool) {
+        unsafe { PxControllerManager_setPreciseSweeps_mut(self.as_mut_ptr(), enabled) }
+    }
+
+    /// Enable or disable preventing controllers from sliding down slopes while they are
+    /// blocked by a ceiling.  Disabled by default.
+    fn set_prevent_vertical_sliding_against_ceiling(&mut self, enabled: bool) {
+        unsafe {
+            PxControllerManager_setPreventVerticalSlidingAgainstCeiling_mut(
+                self.as_mut_ptr(),
+                enabled,
+            )
+        }
+    }
+
+    /// Shift the origin of the controllers and obstacles by `-shift`, when the origin of the
+    /// scene is shifted.
+    fn shift_origin(&mut self, shift: &PxVec3) {
+        unsafe { PxControllerManager_shiftOrigin_mut(self.as_mut_ptr(), shift.as_ptr()) }
+    }
+
+    /// Set what debug geometry the controllers add to the render buffer when they move.
+    fn set_debug_rendering_flags(&mut self, flags: ControllerDebugRenderFlags) {
+        unsafe { PxControllerManager_setDebugRenderingFlags_mut(self.as_mut_ptr(), flags) }
+    }
+
+    /// Get the debug geometry of the controllers.  It accumulates as the controllers move,
+    /// so it should be cleared with [`RenderBuffer::clear`] once drawn.
+    fn get_render_buffer(&mut self) -> &mut RenderBuffer {
+        unsafe {
+            &mut *(PxControllerManager_getRenderBuffer_mut(self.as_mut_ptr()) as *mut RenderBuffer)
+        }
+    }
+}
+
+unsafe impl<C: Controller + Send> Send for PxControllerManager<C> {}
+unsafe impl<C: Controller + Sync> Sync for PxControllerManager<C> {}
+
+impl<C: Controller> Drop for PxControllerManager<C> {
+    fn drop(&mut self) {
+        self.purge_controllers();
+        unsafe { PxControllerManager_release_mut(self.as_mut_ptr()) }
+    }
 }
@@ -1,6 +1,27 @@
-use crate::{owner::Owner, traits::Class};
+use crate::{
+    geometry::MassInformation,
+    math::{PxBounds3, PxVec3},
+    owner::Owner,
+    traits::Class,
+};
 
-use physx_sys::PxConvexMesh_release_mut;
+use std::slice;
+
+#[rustfmt::skip]
+use physx_sys::{
+    PxConvexMesh_getIndexBuffer,
+    PxConvexMesh_getLocalBounds,
+    PxConvexMesh_getMassInformation,
+    PxConvexMesh_getNbPolygons,
+    PxConvexMesh_getNbVertices,
+    PxConvexMesh_getPolygonData,
+    PxConvexMesh_getVertices,
+    PxConvexMesh_isGpuCompatible,
+    PxConvexMesh_release_mut,
+    PxMat33_new,
+};
+
+pub use physx_sys::PxHullPolygon as HullPolygon;
 
 #[repr(transparent)]
 pub struct ConvexMesh {
@@ -18,6 +39,91 @@ impl ConvexMesh {
     pub unsafe fn from_raw(ptr: *mut physx_sys::PxConvexMesh) -> Option<Owner<ConvexMesh>> {
         unsafe { Owner::from_raw(ptr as *mut Self) }
     }
+
+    /// Get the vertices of the hull.
+    pub fn get_vertices(&self) -> &[PxVec3] {
+        unsafe {
+            slice::from_raw_parts(
+                PxConvexMesh_getVertices(self.as_ptr()) as *const PxVec3,
+                PxConvexMesh_getNbVertices(self.as_ptr()) as usize,
+            )
+        }
+    }
+
+    /// Get the number of polygons of the hull.
+    pub fn get_nb_polygons(&self) -> u32 {
+        unsafe { PxConvexMesh_getNbPolygons(self.as_ptr()) }
+    }
+
+    /// Get the plane and vertex index range of a polygon, or None if the index is out of range.
+    pub fn get_polygon_data(&self, index: u32) -> Option<HullPolygon> {
+        let mut polygon = HullPolygon {
+            mPlane: [0.0; 4],
+            mNbVerts: 0,
+            mIndexBase: 0,
+        };
+        unsafe { PxConvexMesh_getPolygonData(self.as_ptr(), index, &mut polygon) }
+            .then_some(polygon)
+    }
+
+    /// Get all the polygons of the hull.
+    pub fn get_polygons(&self) -> Vec<HullPolygon> {
+        (0..self.get_nb_polygons())
+            .filter_map(|index| self.get_polygon_data(index))
+            .collect()
+    }
+
+    /// Get the buffer the polygons index into.  Each entry is an index into the vertices.
+    pub fn get_index_buffer(&self) -> &[u8] {
+        // The hull builder stores the indices of the polygons one after the other, see
+        // GuCookingConvexHullBuilder.cpp:136-139, so the last polygon ends the buffer.
+        let len = self
+            .get_nb_polygons()
+            .checked_sub(1)
+            .and_then(|last| self.get_polygon_data(last))
+            .map_or(0, |polygon| {
+                polygon.mIndexBase as usize + polygon.mNbVerts as usize
+            });
+        unsafe { slice::from_raw_parts(PxConvexMesh_getIndexBuffer(self.as_ptr()), len) }
+    }
+
+    /// Get the vertex indices of a polygon, in winding order.  Returns None if the polygon
+    /// is out of the bounds of the index buffer, for example if it is from another mesh.
+    pub fn get_polygon_indices(&self, polygon: &HullPolygon) -> Option<&[u8]> {
+        let start = polygon.mIndexBase as usize;
+        self.get_index_buffer()
+            .get(start..start + polygon.mNbVerts as usize)
+    }
+
+    /// Get the bounds of the hull in mesh local space.
+    pub fn get_local_bounds(&self) -> PxBounds3 {
+        unsafe { PxConvexMesh_getLocalBounds(self.as_ptr()).into() }
+    }
+
+    /// Get the mass properties of the hull.
+    pub fn get_mass_information(&self) -> MassInformation {
+        let mut mass = 0.0;
+        let mut local_inertia = unsafe { PxMat33_new() };
+        let mut local_center_of_mass = PxVec3::default();
+        unsafe {
+            PxConvexMesh_getMassInformation(
+                self.as_ptr(),
+                &mut mass,
+                &mut local_inertia,
+                local_center_of_mass.as_mut_ptr(),
+            )
+        }
+        MassInformation {
+            mass,
+            local_inertia,
+            local_center_of_mass,
+        }
+    }
+
+    /// Returns true if the hull was cooked for use with GPU rigid bodies.
+    pub fn is_gpu_compatible(&self) -> bool {
+        unsafe { PxConvexMesh_isGpuCompatible(self.as_ptr()) }
+    }
 }
 
 unsafe impl Send for ConvexMesh {}
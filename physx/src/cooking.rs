@@ -1,31 +1,85 @@
+mod decomposition;
+mod diagnostics;
+
 use crate::{
-    bvh::Bvh, convex_mesh::ConvexMesh, height_field::HeightField, owner::Owner, physics::Physics,
-    traits::Class, triangle_mesh::TriangleMesh,
+    bvh::Bvh,
+    convex_mesh::{ConvexMesh, HullPolygon},
+    height_field::{HeightField, HeightFieldFlags},
+    math::{PxBounds3, PxVec3},
+    owner::Owner,
+    physics::Physics,
+    stream::write_to_vec,
+    traits::Class,
+    triangle_mesh::TriangleMesh,
 };
 
+use std::{marker::PhantomData, mem::size_of};
+
+use thiserror::Error;
+
 #[rustfmt::skip]
 use physx_sys::{
+    phys_PxComputeHullPolygons,
+    phys_PxCookBVH,
+    phys_PxCookConvexMesh,
+    phys_PxCookHeightField,
+    phys_PxCookTriangleMesh,
     phys_PxCreateBVH,
     phys_PxCreateConvexMesh,
     phys_PxCreateHeightField,
     phys_PxCreateTriangleMesh,
+    phys_PxGetAllocatorCallback,
     phys_PxValidateConvexMesh,
     phys_PxValidateTriangleMesh,
     PxBVHDesc_isValid,
     PxBVHDesc_new,
     PxBVHDesc_setToDefault_mut,
+    PxBoundedData,
+    PxBoundedData_new,
     PxConvexMeshDesc_isValid,
     PxConvexMeshDesc_new,
     PxConvexMeshDesc_setToDefault_mut,
+    PxCookingParams_getMidphaseBVH33,
+    PxCookingParams_getMidphaseBVH34,
     PxCookingParams_new,
+    PxCookingParams_setMidphaseBVH33,
+    PxCookingParams_setMidphaseBVH34,
     PxHeightFieldDesc_isValid,
     PxHeightFieldDesc_new,
     PxHeightFieldDesc_setToDefault_mut,
+    PxAllocatorCallback_deallocate_mut,
+    PxMidphaseDesc_getType,
+    PxMidphaseDesc_isValid,
+    PxSDFDesc_isValid,
+    PxSDFDesc_new,
+    PxSimpleTriangleMesh_new,
+    PxTriangleMeshDesc_getMaterialIndices,
     PxTriangleMeshDesc_isValid,
     PxTriangleMeshDesc_new,
+    PxTriangleMeshDesc_setMaterialIndices,
     PxTriangleMeshDesc_setToDefault_mut,
 };
 
+pub(crate) use self::diagnostics::register_listener;
+use self::diagnostics::{capture_messages, LARGE_TRIANGLES_MESSAGE};
+
+pub use self::decomposition::{create_convex_meshes, ConvexDecomposition};
+pub use self::diagnostics::{CookingError, CookingErrorKind, CookingMessage, CookingOutput};
+
+#[rustfmt::skip]
+pub use physx_sys::{
+    PxBVH34BuildStrategy as BVH34BuildStrategy,
+    PxBVHBuildStrategy as BVHBuildStrategy,
+    PxConvexFlag as ConvexFlag,
+    PxConvexFlags as ConvexFlags,
+    PxMeshFlag as MeshFlag,
+    PxMeshFlags as MeshFlags,
+    PxMeshMidPhase as MeshMidPhase,
+    PxMeshPreprocessingFlag as MeshPreprocessingFlag,
+    PxMeshPreprocessingFlags as MeshPreprocessingFlags,
+    PxSdfBitsPerSubgridPixel as SdfBitsPerSubgridPixel,
+};
+
 /// Cooks and creates a bounding volume hierarchy without going through a stream.
 ///
 /// This method does the same as `cook_bvh`, but the produced BVH is not stored
@@ -33,7 +87,10 @@ use physx_sys::{
 ///
 /// A [`crate::Bvh`] object on success
 #[inline]
-pub fn create_bvh(physics: &mut impl Physics, desc: &PxBVHDesc) -> Option<Owner<Bvh>> {
+pub fn create_bvh(physics: &mut impl Physics, desc: &PxBVHDesc<'_>) -> Option<Owner<Bvh>> {
+    if !desc.is_valid() {
+        return None;
+    }
     unsafe {
         Bvh::from_raw(phys_PxCreateBVH(
             desc.as_ptr(),
@@ -50,7 +107,7 @@ pub fn create_bvh(physics: &mut impl Physics, desc: &PxBVHDesc) -> Option<Owner<
 pub fn create_convex_mesh(
     physics: &mut impl Physics,
     params: &PxCookingParams,
-    desc: &PxConvexMeshDesc,
+    desc: &PxConvexMeshDesc<'_>,
 ) -> ConvexMeshCookingResult {
     if !desc.is_valid() {
         return ConvexMeshCookingResult::InvalidDescriptor;
@@ -77,8 +134,11 @@ pub fn create_convex_mesh(
 #[inline]
 pub fn create_height_field(
     physics: &mut impl Physics,
-    desc: &PxHeightFieldDesc,
+    desc: &PxHeightFieldDesc<'_>,
 ) -> Option<Owner<HeightField>> {
+    if !desc.is_valid() {
+        return None;
+    }
     unsafe {
         HeightField::from_raw(phys_PxCreateHeightField(
             desc.as_ptr(),
@@ -95,7 +155,7 @@ pub fn create_height_field(
 pub fn create_triangle_mesh(
     physics: &mut impl Physics,
     params: &PxCookingParams,
-    desc: &PxTriangleMeshDesc,
+    desc: &PxTriangleMeshDesc<'_>,
 ) -> TriangleMeshCookingResult {
     if !desc.is_valid() {
         return TriangleMeshCookingResult::InvalidDescriptor;
@@ -116,9 +176,59 @@ pub fn create_triangle_mesh(
     }
 }
 
+/// Cooks a bounding volume hierarchy.  The result can be loaded with
+/// [`Physics::load_bvh_from_bytes`].
+pub fn cook_bvh_to_vec(desc: &PxBVHDesc<'_>) -> Option<Vec<u8>> {
+    if !desc.is_valid() {
+        return None;
+    }
+    write_to_vec(|stream| unsafe { phys_PxCookBVH(desc.as_ptr(), stream) })
+}
+
+/// Cooks a convex mesh.  The result can be loaded with
+/// [`Physics::load_convex_mesh_from_bytes`].
+pub fn cook_convex_mesh_to_vec(
+    params: &PxCookingParams,
+    desc: &PxConvexMeshDesc<'_>,
+) -> ConvexMeshCookingResult<Vec<u8>> {
+    if !desc.is_valid() {
+        return ConvexMeshCookingResult::InvalidDescriptor;
+    };
+    let mut result = ConvRes::Failure;
+    let data = write_to_vec(|stream| unsafe {
+        phys_PxCookConvexMesh(params.as_ptr(), desc.as_ptr(), stream, &mut result)
+    });
+    ConvexMeshCookingResult::from_raw(result, data)
+}
+
+/// Cooks a height field.  The result can be loaded with
+/// [`Physics::load_height_field_from_bytes`].
+pub fn cook_height_field_to_vec(desc: &PxHeightFieldDesc<'_>) -> Option<Vec<u8>> {
+    if !desc.is_valid() {
+        return None;
+    }
+    write_to_vec(|stream| unsafe { phys_PxCookHeightField(desc.as_ptr(), stream) })
+}
+
+/// Cooks a triangle mesh.  The result can be loaded with
+/// [`Physics::load_triangle_mesh_from_bytes`].
+pub fn cook_triangle_mesh_to_vec(
+    params: &PxCookingParams,
+    desc: &PxTriangleMeshDesc<'_>,
+) -> TriangleMeshCookingResult<Vec<u8>> {
+    if !desc.is_valid() {
+        return TriangleMeshCookingResult::InvalidDescriptor;
+    };
+    let mut result = TriResult::Failure;
+    let data = write_to_vec(|stream| unsafe {
+        phys_PxCookTriangleMesh(params.as_ptr(), desc.as_ptr(), stream, &mut result)
+    });
+    TriangleMeshCookingResult::from_raw(result, data)
+}
+
 /// Validate a convex mesh descriptor.
 #[inline]
-pub fn validate_convex_mesh(params: &PxCookingParams, desc: &PxConvexMeshDesc) -> bool {
+pub fn validate_convex_mesh(params: &PxCookingParams, desc: &PxConvexMeshDesc<'_>) -> bool {
     unsafe { phys_PxValidateConvexMesh(params.as_ptr(), desc.as_ptr()) }
 }
 
@@ -130,22 +240,246 @@ pub fn validate_convex_mesh(params: &PxCookingParams, desc: &PxConvexMeshDesc) -
 ///
 /// true if all the validity conditions hold, false otherwise.
 #[inline]
-pub fn validate_triangle_mesh(params: &PxCookingParams, desc: &PxTriangleMeshDesc) -> bool {
+pub fn validate_triangle_mesh(params: &PxCookingParams, desc: &PxTriangleMeshDesc<'_>) -> bool {
     unsafe { phys_PxValidateTriangleMesh(params.as_ptr(), desc.as_ptr()) }
 }
 
+/// Cooks and creates a convex mesh, capturing the messages PhysX reports while cooking.
+pub fn try_create_convex_mesh(
+    physics: &mut impl Physics,
+    params: &PxCookingParams,
+    desc: &PxConvexMeshDesc<'_>,
+) -> Result<CookingOutput<Owner<ConvexMesh>>, CookingError> {
+    capture_messages(|| {
+        check_convex_mesh_desc(params, desc)?;
+        let callback = physics
+            .get_physics_insertion_callback()
+            .ok_or(CookingErrorKind::NoInsertionCallback)?;
+        let mut result = ConvRes::Failure;
+        let ptr = unsafe {
+            ConvexMesh::from_raw(phys_PxCreateConvexMesh(
+                params.as_ptr(),
+                desc.as_ptr(),
+                callback,
+                &mut result,
+            ))
+        };
+        ConvexMeshCookingResult::from_raw(result, ptr).into_result()
+    })
+}
+
+/// Cooks and creates a triangle mesh, capturing the messages PhysX reports while cooking.
+pub fn try_create_triangle_mesh(
+    physics: &mut impl Physics,
+    params: &PxCookingParams,
+    desc: &PxTriangleMeshDesc<'_>,
+) -> Result<CookingOutput<Owner<TriangleMesh>>, CookingError> {
+    capture_messages(|| {
+        check_triangle_mesh_desc(params, desc)?;
+        let callback = physics
+            .get_physics_insertion_callback()
+            .ok_or(CookingErrorKind::NoInsertionCallback)?;
+        let mut result = TriResult::Failure;
+        let ptr = unsafe {
+            TriangleMesh::from_raw(phys_PxCreateTriangleMesh(
+                params.as_ptr(),
+                desc.as_ptr(),
+                callback,
+                &mut result,
+            ))
+        };
+        TriangleMeshCookingResult::from_raw(result, ptr).into_result()
+    })
+}
+
+/// Cooks a convex mesh into bytes, capturing the messages PhysX reports while cooking.
+pub fn try_cook_convex_mesh_to_vec(
+    params: &PxCookingParams,
+    desc: &PxConvexMeshDesc<'_>,
+) -> Result<CookingOutput<Vec<u8>>, CookingError> {
+    capture_messages(|| {
+        check_convex_mesh_desc(params, desc)?;
+        let mut result = ConvRes::Failure;
+        let data = write_to_vec(|stream| unsafe {
+            phys_PxCookConvexMesh(params.as_ptr(), desc.as_ptr(), stream, &mut result)
+        });
+        ConvexMeshCookingResult::from_raw(result, data).into_result()
+    })
+}
+
+/// Cooks a triangle mesh into bytes, capturing the messages PhysX reports while cooking.
+pub fn try_cook_triangle_mesh_to_vec(
+    params: &PxCookingParams,
+    desc: &PxTriangleMeshDesc<'_>,
+) -> Result<CookingOutput<Vec<u8>>, CookingError> {
+    capture_messages(|| {
+        check_triangle_mesh_desc(params, desc)?;
+        let mut result = TriResult::Failure;
+        let data = write_to_vec(|stream| unsafe {
+            phys_PxCookTriangleMesh(params.as_ptr(), desc.as_ptr(), stream, &mut result)
+        });
+        TriangleMeshCookingResult::from_raw(result, data).into_result()
+    })
+}
+
+/// Validate a convex mesh descriptor like [`validate_convex_mesh`], reporting why it is
+/// invalid and the messages PhysX reported.
+pub fn check_convex_mesh(
+    params: &PxCookingParams,
+    desc: &PxConvexMeshDesc<'_>,
+) -> Result<CookingOutput<()>, CookingError> {
+    capture_messages(|| {
+        check_convex_mesh_desc(params, desc)?;
+        if unsafe { phys_PxValidateConvexMesh(params.as_ptr(), desc.as_ptr()) } {
+            Ok(())
+        } else {
+            Err(CookingErrorKind::Failure)
+        }
+    })
+}
+
+/// Validate a triangle mesh descriptor like [`validate_triangle_mesh`], reporting which
+/// condition does not hold and the messages PhysX reported.
+pub fn check_triangle_mesh(
+    params: &PxCookingParams,
+    desc: &PxTriangleMeshDesc<'_>,
+) -> Result<CookingOutput<()>, CookingError> {
+    let output = capture_messages(|| {
+        check_triangle_mesh_desc(params, desc)?;
+        if unsafe { phys_PxValidateTriangleMesh(params.as_ptr(), desc.as_ptr()) } {
+            Ok(())
+        } else {
+            // Validation fails without a message when cleaning the mesh would weld
+            // vertices or remove triangles.
+            Err(CookingErrorKind::DuplicateVertices)
+        }
+    })?;
+    // Large triangles only produce a warning, they do not fail the validation.
+    if output
+        .messages
+        .iter()
+        .any(|message| message.message.contains(LARGE_TRIANGLES_MESSAGE))
+    {
+        return Err(CookingError {
+            kind: CookingErrorKind::LargeTriangles,
+            messages: output.messages,
+        });
+    }
+    Ok(output)
+}
+
+fn check_convex_mesh_desc(
+    params: &PxCookingParams,
+    desc: &PxConvexMeshDesc<'_>,
+) -> Result<(), CookingErrorKind> {
+    desc.validate()?;
+    if params.is_valid() && unsafe { PxConvexMeshDesc_isValid(desc.as_ptr()) } {
+        Ok(())
+    } else {
+        Err(CookingErrorKind::RejectedDescriptor)
+    }
+}
+
+fn check_triangle_mesh_desc(
+    params: &PxCookingParams,
+    desc: &PxTriangleMeshDesc<'_>,
+) -> Result<(), CookingErrorKind> {
+    desc.validate()?;
+    if params.is_valid() && unsafe { PxTriangleMeshDesc_isValid(desc.as_ptr()) } {
+        Ok(())
+    } else {
+        Err(CookingErrorKind::RejectedDescriptor)
+    }
+}
+
+/// The polygons of a hull, as computed by [`compute_hull_polygons`].  Pass them to
+/// [`PxConvexMeshDesc::set_points`] and [`PxConvexMeshDesc::set_polygons`].
+#[derive(Clone, Default)]
+pub struct HullPolygons {
+    pub vertices: Vec<PxVec3>,
+    pub indices: Vec<u32>,
+    pub polygons: Vec<HullPolygon>,
+}
+
+/// Compute the polygons of a hull from its triangles, merging coplanar triangles.  The
+/// triangles must already form a closed convex mesh; use [`create_convex_meshes`] to
+/// compute hulls for arbitrary meshes.  Returns `None` if a triangle index is out of
+/// range of the points, or if PhysX fails.
+pub fn compute_hull_polygons(
+    params: &PxCookingParams,
+    points: &[PxVec3],
+    triangles: &[[u32; 3]],
+) -> Option<HullPolygons> {
+    unsafe {
+        let mut mesh = PxSimpleTriangleMesh_new();
+        mesh.points = bounded_data(points);
+        mesh.triangles = bounded_data(triangles);
+        check_indices(
+            "triangles",
+            &mesh.triangles,
+            3,
+            false,
+            u32::try_from(points.len()).ok()?,
+        )
+        .ok()?;
+        let allocator = phys_PxGetAllocatorCallback();
+        let mut nb_vertices = 0;
+        let mut vertices = std::ptr::null_mut();
+        let mut nb_indices = 0;
+        let mut indices = std::ptr::null_mut();
+        let mut nb_polygons = 0;
+        let mut polygons = std::ptr::null_mut();
+        if !phys_PxComputeHullPolygons(
+            params.as_ptr(),
+            &mesh,
+            allocator,
+            &mut nb_vertices,
+            &mut vertices,
+            &mut nb_indices,
+            &mut indices,
+            &mut nb_polygons,
+            &mut polygons,
+        ) {
+            return None;
+        }
+        let hull = HullPolygons {
+            vertices: copy_and_free(allocator, vertices.cast::<PxVec3>(), nb_vertices),
+            indices: copy_and_free(allocator, indices, nb_indices),
+            polygons: copy_and_free(allocator, polygons, nb_polygons),
+        };
+        Some(hull)
+    }
+}
+
+/// Copy an array PhysX allocated with `allocator` and free it.
+unsafe fn copy_and_free<T: Copy>(
+    allocator: *mut physx_sys::PxAllocatorCallback,
+    ptr: *mut T,
+    len: u32,
+) -> Vec<T> {
+    if ptr.is_null() {
+        return Vec::new();
+    }
+    unsafe {
+        let copy = std::slice::from_raw_parts(ptr, len as usize).to_vec();
+        PxAllocatorCallback_deallocate_mut(allocator, ptr.cast());
+        copy
+    }
+}
+
 use physx_sys::PxConvexMeshCookingResult as ConvRes;
 
-pub enum ConvexMeshCookingResult {
-    Success(Owner<ConvexMesh>),
+/// The result of cooking a convex mesh, either into `Physics` or into bytes.
+pub enum ConvexMeshCookingResult<T = Owner<ConvexMesh>> {
+    Success(T),
     ZeroAreaTestFailed,
     PolygonsLimitReached,
     Failure,
     InvalidDescriptor,
 }
 
-impl ConvexMeshCookingResult {
-    fn from_raw(px_result: ConvRes, ptr: Option<Owner<ConvexMesh>>) -> Self {
+impl<T> ConvexMeshCookingResult<T> {
+    fn from_raw(px_result: ConvRes, ptr: Option<T>) -> Self {
         match px_result {
             ConvRes::Success => {
                 if let Some(ptr) = ptr {
@@ -159,19 +493,31 @@ impl ConvexMeshCookingResult {
             ConvRes::Failure => Self::Failure,
         }
     }
+
+    /// Convert into a `Result`, with the variant that is not a success as the error.
+    pub fn into_result(self) -> Result<T, CookingErrorKind> {
+        match self {
+            Self::Success(value) => Ok(value),
+            Self::ZeroAreaTestFailed => Err(CookingErrorKind::ZeroAreaTestFailed),
+            Self::PolygonsLimitReached => Err(CookingErrorKind::PolygonsLimitReached),
+            Self::Failure => Err(CookingErrorKind::Failure),
+            Self::InvalidDescriptor => Err(CookingErrorKind::RejectedDescriptor),
+        }
+    }
 }
 
 use physx_sys::PxTriangleMeshCookingResult as TriResult;
 
-pub enum TriangleMeshCookingResult {
-    Success(Owner<TriangleMesh>),
+/// The result of cooking a triangle mesh, either into `Physics` or into bytes.
+pub enum TriangleMeshCookingResult<T = Owner<TriangleMesh>> {
+    Success(T),
     LargeTriangle,
     Failure,
     InvalidDescriptor,
 }
 
-impl TriangleMeshCookingResult {
-    fn from_raw(px_result: TriResult, ptr: Option<Owner<TriangleMesh>>) -> Self {
+impl<T> TriangleMeshCookingResult<T> {
+    fn from_raw(px_result: TriResult, ptr: Option<T>) -> Self {
         match px_result {
             TriResult::Success => {
                 if let Some(ptr) = ptr {
@@ -184,13 +530,130 @@ impl TriangleMeshCookingResult {
             TriResult::Failure => Self::Failure,
         }
     }
+
+    /// Convert into a `Result`, with the variant that is not a success as the error.
+    pub fn into_result(self) -> Result<T, CookingErrorKind> {
+        match self {
+            Self::Success(value) => Ok(value),
+            Self::LargeTriangle => Err(CookingErrorKind::LargeTriangles),
+            Self::Failure => Err(CookingErrorKind::Failure),
+            Self::InvalidDescriptor => Err(CookingErrorKind::RejectedDescriptor),
+        }
+    }
 }
 
-pub struct PxConvexMeshDesc {
-    pub obj: physx_sys::PxConvexMeshDesc,
+#[derive(Error, Debug)]
+pub enum MeshDescriptorError {
+    #[error("{name} has {count} elements but no data")]
+    MissingData { name: &'static str, count: u32 },
+
+    #[error("{name} has a stride of {stride} bytes, but an element is {min_stride} bytes")]
+    StrideTooSmall {
+        name: &'static str,
+        stride: u32,
+        min_stride: u32,
+    },
+
+    #[error("Index {index} of element {element} of {name} is out of range, the limit is {limit}")]
+    IndexOutOfRange {
+        name: &'static str,
+        element: usize,
+        index: u32,
+        limit: u32,
+    },
+
+    #[error("There are {nb_material_indices} material indices for {nb_triangles} triangles")]
+    MaterialCountMismatch {
+        nb_material_indices: usize,
+        nb_triangles: usize,
+    },
+
+    #[error("{nb_samples} samples do not fill {nb_rows} rows of {nb_columns} columns")]
+    SampleCountMismatch {
+        nb_samples: usize,
+        nb_rows: u32,
+        nb_columns: u32,
+    },
+
+    #[error("The signed distance field descriptor is invalid")]
+    InvalidSdf,
 }
 
-unsafe impl Class<physx_sys::PxConvexMeshDesc> for PxConvexMeshDesc {
+/// Point a PxBoundedData at a slice.
+fn bounded_data<T>(data: &[T]) -> PxBoundedData {
+    let mut bounded = unsafe { PxBoundedData_new() };
+    bounded.data = data.as_ptr().cast();
+    bounded.stride = size_of::<T>() as u32;
+    bounded.count = u32::try_from(data.len()).expect("too many elements for PhysX");
+    bounded
+}
+
+/// Check that `data` has data if it has elements, and that its stride fits an element.
+fn check_bounded_data(
+    name: &'static str,
+    data: &PxBoundedData,
+    element_size: usize,
+) -> Result<(), MeshDescriptorError> {
+    if data.count == 0 {
+        Ok(())
+    } else if data.data.is_null() {
+        Err(MeshDescriptorError::MissingData {
+            name,
+            count: data.count,
+        })
+    } else if (data.stride as usize) < element_size {
+        Err(MeshDescriptorError::StrideTooSmall {
+            name,
+            stride: data.stride,
+            min_stride: element_size as u32,
+        })
+    } else {
+        Ok(())
+    }
+}
+
+/// Check that the indices in `data`, `per_element` 16 or 32 bit indices per element,
+/// are all below `limit`.
+///
+/// # Safety
+/// `data` must have passed `check_bounded_data` and point to valid memory.
+unsafe fn check_indices(
+    name: &'static str,
+    data: &PxBoundedData,
+    per_element: usize,
+    is_16_bit: bool,
+    limit: u32,
+) -> Result<(), MeshDescriptorError> {
+    for element in 0..data.count as usize {
+        let base = data.data.cast::<u8>().add(element * data.stride as usize);
+        for offset in 0..per_element {
+            let index = if is_16_bit {
+                base.cast::<u16>().add(offset).read_unaligned() as u32
+            } else {
+                base.cast::<u32>().add(offset).read_unaligned()
+            };
+            if index >= limit {
+                return Err(MeshDescriptorError::IndexOutOfRange {
+                    name,
+                    element,
+                    index,
+                    limit,
+                });
+            }
+        }
+    }
+    Ok(())
+}
+
+/// A convex mesh descriptor.  The data is borrowed for `'a`, use the setters to
+/// fill it in from slices.  Fields without a setter can still be written through
+/// [`Class::as_mut_ptr`], at the cost of the borrow and `validate` checks.
+pub struct PxConvexMeshDesc<'a> {
+    pub(crate) obj: physx_sys::PxConvexMeshDesc,
+    phantom_data: PhantomData<&'a ()>,
+}
+
+unsafe impl Class<physx_sys::PxConvexMeshDesc> for PxConvexMeshDesc<'_> {
     fn as_ptr(&self) -> *const physx_sys::PxConvexMeshDesc {
         &self.obj
     }
@@ -200,25 +663,124 @@ unsafe impl Class<physx_sys::PxConvexMeshDesc> for PxConvexMeshDesc {
     }
 }
 
-impl Default for PxConvexMeshDesc {
+impl Default for PxConvexMeshDesc<'_> {
     fn default() -> Self {
         Self::new()
     }
 }
 
-impl PxConvexMeshDesc {
+impl<'a> PxConvexMeshDesc<'a> {
     /// Create a new convex mesh descriptor.
     pub fn new() -> Self {
         unsafe {
             Self {
                 obj: PxConvexMeshDesc_new(),
+                phantom_data: PhantomData,
+            }
+        }
+    }
+
+    /// Set the vertices of the hull.  With [`ConvexFlag::ComputeConvex`] these can be any point
+    /// cloud, the hull is computed from them.
+    pub fn set_points(&mut self, points: &'a [PxVec3]) -> &mut Self {
+        self.obj.points = bounded_data(points);
+        self
+    }
+
+    /// Set the polygons of the hull and the 32 bit vertex indices they index into.
+    pub fn set_polygons(&mut self, polygons: &'a [HullPolygon], indices: &'a [u32]) -> &mut Self {
+        self.obj.polygons = bounded_data(polygons);
+        self.obj.indices = bounded_data(indices);
+        self.obj.flags.remove(ConvexFlags::E16BitIndices);
+        self
+    }
+
+    /// Set the polygons of the hull and the 16 bit vertex indices they index into.
+    pub fn set_polygons_u16(
+        &mut self,
+        polygons: &'a [HullPolygon],
+        indices: &'a [u16],
+    ) -> &mut Self {
+        self.obj.polygons = bounded_data(polygons);
+        self.obj.indices = bounded_data(indices);
+        self.obj.flags.insert(ConvexFlags::E16BitIndices);
+        self
+    }
+
+    /// Set the flags, keeping the index size chosen by the polygon setters.
+    pub fn set_flags(&mut self, flags: ConvexFlags) -> &mut Self {
+        let index_size = self.obj.flags & ConvexFlags::E16BitIndices;
+        self.obj.flags = (flags - ConvexFlags::E16BitIndices) | index_size;
+        self
+    }
+
+    /// Limit the number of vertices of a computed hull, at least 4 and at most 255.
+    pub fn set_vertex_limit(&mut self, vertex_limit: u16) -> &mut Self {
+        self.obj.vertexLimit = vertex_limit;
+        self
+    }
+
+    /// Limit the number of polygons of a computed hull, at least 4 and at most 255.
+    pub fn set_polygon_limit(&mut self, polygon_limit: u16) -> &mut Self {
+        self.obj.polygonLimit = polygon_limit;
+        self
+    }
+
+    /// Reduce the input points to `quantized_count` points with K-means clustering before
+    /// the hull is computed, or use the points as they are with `None`.  This sets or clears
+    /// [`ConvexFlag::QuantizeInput`], so call it after [`Self::set_flags`].
+    pub fn set_quantized_input(&mut self, quantized_count: Option<u16>) -> &mut Self {
+        match quantized_count {
+            Some(quantized_count) => {
+                self.obj.quantizedCount = quantized_count;
+                self.obj.flags.insert(ConvexFlags::QuantizeInput);
+            }
+            None => self.obj.flags.remove(ConvexFlags::QuantizeInput),
+        }
+        self
+    }
+
+    /// Check that the strides fit the data and that all indices are in range.
+    pub fn validate(&self) -> Result<(), MeshDescriptorError> {
+        let is_16_bit = self.obj.flags.contains(ConvexFlags::E16BitIndices);
+        let index_size = if is_16_bit { 2 } else { 4 };
+        check_bounded_data("points", &self.obj.points, size_of::<PxVec3>())?;
+        check_bounded_data("polygons", &self.obj.polygons, size_of::<HullPolygon>())?;
+        check_bounded_data("indices", &self.obj.indices, index_size)?;
+        unsafe {
+            check_indices(
+                "indices",
+                &self.obj.indices,
+                1,
+                is_16_bit,
+                self.obj.points.count,
+            )?;
+            for polygon in 0..self.obj.polygons.count as usize {
+                let polygon_ptr = self
+                    .obj
+                    .polygons
+                    .data
+                    .cast::<u8>()
+                    .add(polygon * self.obj.polygons.stride as usize)
+                    .cast::<HullPolygon>();
+                let polygon_data = polygon_ptr.read_unaligned();
+                let end = polygon_data.mIndexBase as u32 + polygon_data.mNbVerts as u32;
+                if end > self.obj.indices.count {
+                    return Err(MeshDescriptorError::IndexOutOfRange {
+                        name: "polygons",
+                        element: polygon,
+                        index: end - 1,
+                        limit: self.obj.indices.count,
+                    });
+                }
             }
         }
+        Ok(())
     }
 
     /// Check if the descriptor is valid.
     pub fn is_valid(&self) -> bool {
-        unsafe { PxConvexMeshDesc_isValid(self.as_ptr()) }
+        self.validate().is_ok() && unsafe { PxConvexMeshDesc_isValid(self.as_ptr()) }
     }
 
     /// Set the descriptor to its default values.
@@ -230,11 +792,15 @@ impl PxConvexMeshDesc {
     }
 }
 
-pub struct PxTriangleMeshDesc {
-    pub obj: physx_sys::PxTriangleMeshDesc,
+/// A triangle mesh descriptor.  The data is borrowed for `'a`, use the setters to
+/// fill it in from slices.
+pub struct PxTriangleMeshDesc<'a> {
+    pub(crate) obj: physx_sys::PxTriangleMeshDesc,
+    nb_material_indices: Option<usize>,
+    phantom_data: PhantomData<&'a ()>,
 }
 
-unsafe impl Class<physx_sys::PxTriangleMeshDesc> for PxTriangleMeshDesc {
+unsafe impl Class<physx_sys::PxTriangleMeshDesc> for PxTriangleMeshDesc<'_> {
     fn as_ptr(&self) -> *const physx_sys::PxTriangleMeshDesc {
         &self.obj
     }
@@ -244,25 +810,124 @@ unsafe impl Class<physx_sys::PxTriangleMeshDesc> for PxTriangleMeshDesc {
     }
 }
 
-impl Default for PxTriangleMeshDesc {
+impl Default for PxTriangleMeshDesc<'_> {
     fn default() -> Self {
         Self::new()
     }
 }
 
-impl PxTriangleMeshDesc {
+impl<'a> PxTriangleMeshDesc<'a> {
     /// Create a new triangle mesh descriptor.
     pub fn new() -> Self {
         unsafe {
             Self {
                 obj: PxTriangleMeshDesc_new(),
+                nb_material_indices: None,
+                phantom_data: PhantomData,
+            }
+        }
+    }
+
+    /// Set the vertices of the mesh.
+    pub fn set_points(&mut self, points: &'a [PxVec3]) -> &mut Self {
+        self.obj.points = bounded_data(points);
+        self
+    }
+
+    /// Set the triangles as 32 bit indices into the points.
+    pub fn set_triangles(&mut self, triangles: &'a [[u32; 3]]) -> &mut Self {
+        self.obj.triangles = bounded_data(triangles);
+        self.obj.flags.remove(MeshFlags::E16BitIndices);
+        self
+    }
+
+    /// Set the triangles as 16 bit indices into the points.
+    pub fn set_triangles_u16(&mut self, triangles: &'a [[u16; 3]]) -> &mut Self {
+        self.obj.triangles = bounded_data(triangles);
+        self.obj.flags.insert(MeshFlags::E16BitIndices);
+        self
+    }
+
+    /// Set a material index per triangle, which index into the materials of the shapes
+    /// the mesh is used in.
+    pub fn set_material_indices(&mut self, material_indices: &'a [u16]) -> &mut Self {
+        unsafe {
+            PxTriangleMeshDesc_setMaterialIndices(
+                self.as_mut_ptr(),
+                material_indices.as_ptr(),
+                size_of::<u16>() as u32,
+            );
+        }
+        self.nb_material_indices = Some(material_indices.len());
+        self
+    }
+
+    /// Set the flags, keeping the index size chosen by the triangle setters.
+    pub fn set_flags(&mut self, flags: MeshFlags) -> &mut Self {
+        let index_size = self.obj.flags & MeshFlags::E16BitIndices;
+        self.obj.flags = (flags - MeshFlags::E16BitIndices) | index_size;
+        self
+    }
+
+    /// Generate a signed distance field for the mesh when it is cooked, as described by `sdf`.
+    pub fn set_sdf(&mut self, sdf: &'a mut PxSDFDesc) -> &mut Self {
+        self.obj.sdfDesc = sdf.as_mut_ptr();
+        self
+    }
+
+    /// Do not generate a signed distance field.
+    pub fn clear_sdf(&mut self) -> &mut Self {
+        self.obj.sdfDesc = std::ptr::null_mut();
+        self
+    }
+
+    /// Check that the strides fit the data, that all indices are in range and that
+    /// there is a material index for every triangle.
+    pub fn validate(&self) -> Result<(), MeshDescriptorError> {
+        let is_16_bit = self.obj.flags.contains(MeshFlags::E16BitIndices);
+        let index_size = if is_16_bit { 2 } else { 4 };
+        check_bounded_data("points", &self.obj.points, size_of::<PxVec3>())?;
+        check_bounded_data("triangles", &self.obj.triangles, 3 * index_size)?;
+        unsafe {
+            check_indices(
+                "triangles",
+                &self.obj.triangles,
+                3,
+                is_16_bit,
+                self.obj.points.count,
+            )?;
+            if let Some(sdf) = self.obj.sdfDesc.as_ref() {
+                if !PxSDFDesc_isValid(sdf) {
+                    return Err(MeshDescriptorError::InvalidSdf);
+                }
+            }
+            let mut stride = 0;
+            let material_indices =
+                PxTriangleMeshDesc_getMaterialIndices(self.as_ptr(), &mut stride);
+            if !material_indices.is_null() && (stride as usize) < size_of::<u16>() {
+                return Err(MeshDescriptorError::StrideTooSmall {
+                    name: "material indices",
+                    stride,
+                    min_stride: size_of::<u16>() as u32,
+                });
+            }
+        }
+        match self.nb_material_indices {
+            Some(nb_material_indices)
+                if nb_material_indices != self.obj.triangles.count as usize =>
+            {
+                Err(MeshDescriptorError::MaterialCountMismatch {
+                    nb_material_indices,
+                    nb_triangles: self.obj.triangles.count as usize,
+                })
             }
+            _ => Ok(()),
         }
     }
 
     /// Check if the descriptor is valid.
     pub fn is_valid(&self) -> bool {
-        unsafe { PxTriangleMeshDesc_isValid(self.as_ptr()) }
+        self.validate().is_ok() && unsafe { PxTriangleMeshDesc_isValid(self.as_ptr()) }
     }
 
     /// Set the descriptor to its default values.
@@ -270,15 +935,20 @@ impl PxTriangleMeshDesc {
         unsafe {
             PxTriangleMeshDesc_setToDefault_mut(self.as_mut_ptr());
         }
+        self.nb_material_indices = None;
         self
     }
 }
 
-pub struct PxHeightFieldDesc {
-    pub obj: physx_sys::PxHeightFieldDesc,
+/// A height field descriptor.  The samples are borrowed for `'a`, use
+/// [`PxHeightFieldDesc::set_samples`] to fill them in from a slice.
+pub struct PxHeightFieldDesc<'a> {
+    pub(crate) obj: physx_sys::PxHeightFieldDesc,
+    nb_samples: Option<usize>,
+    phantom_data: PhantomData<&'a ()>,
 }
 
-unsafe impl Class<physx_sys::PxHeightFieldDesc> for PxHeightFieldDesc {
+unsafe impl Class<physx_sys::PxHeightFieldDesc> for PxHeightFieldDesc<'_> {
     fn as_ptr(&self) -> *const physx_sys::PxHeightFieldDesc {
         &self.obj
     }
@@ -288,25 +958,85 @@ unsafe impl Class<physx_sys::PxHeightFieldDesc> for PxHeightFieldDesc {
     }
 }
 
-impl Default for PxHeightFieldDesc {
+impl Default for PxHeightFieldDesc<'_> {
     fn default() -> Self {
         Self::new()
     }
 }
 
-impl PxHeightFieldDesc {
+impl<'a> PxHeightFieldDesc<'a> {
     /// Create a new height field descriptor.
     pub fn new() -> Self {
         unsafe {
             Self {
                 obj: PxHeightFieldDesc_new(),
+                nb_samples: None,
+                phantom_data: PhantomData,
+            }
+        }
+    }
+
+    /// Set the samples, row by row, with `nb_columns` samples per row.  The number of
+    /// rows is the number of samples divided by `nb_columns`.
+    /// Use [`HeightFieldSample`](crate::height_field::HeightFieldSample) to build the samples.
+    pub fn set_samples(
+        &mut self,
+        nb_columns: u32,
+        samples: &'a [physx_sys::PxHeightFieldSample],
+    ) -> &mut Self {
+        self.obj.nbColumns = nb_columns;
+        self.obj.nbRows = samples.len().checked_div(nb_columns as usize).unwrap_or(0) as u32;
+        self.obj.samples.data = samples.as_ptr().cast();
+        self.obj.samples.stride = size_of::<physx_sys::PxHeightFieldSample>() as u32;
+        self.nb_samples = Some(samples.len());
+        self
+    }
+
+    /// Set the height difference below which edges are treated as convex, to reduce
+    /// collisions with internal edges.
+    pub fn set_convex_edge_threshold(&mut self, threshold: f32) -> &mut Self {
+        self.obj.convexEdgeThreshold = threshold;
+        self
+    }
+
+    /// Set the flags.
+    pub fn set_flags(&mut self, flags: HeightFieldFlags) -> &mut Self {
+        self.obj.flags = flags;
+        self
+    }
+
+    /// Check that the stride fits the samples and that the samples fill the rows and columns.
+    pub fn validate(&self) -> Result<(), MeshDescriptorError> {
+        let nb_samples = self.obj.nbRows * self.obj.nbColumns;
+        if nb_samples > 0 && self.obj.samples.data.is_null() {
+            return Err(MeshDescriptorError::MissingData {
+                name: "samples",
+                count: nb_samples,
+            });
+        }
+        let sample_size = size_of::<physx_sys::PxHeightFieldSample>() as u32;
+        if self.obj.samples.stride < sample_size {
+            return Err(MeshDescriptorError::StrideTooSmall {
+                name: "samples",
+                stride: self.obj.samples.stride,
+                min_stride: sample_size,
+            });
+        }
+        match self.nb_samples {
+            Some(len) if len != nb_samples as usize => {
+                Err(MeshDescriptorError::SampleCountMismatch {
+                    nb_samples: len,
+                    nb_rows: self.obj.nbRows,
+                    nb_columns: self.obj.nbColumns,
+                })
             }
+            _ => Ok(()),
         }
     }
 
     /// Check if the descriptor is valid.
     pub fn is_valid(&self) -> bool {
-        unsafe { PxHeightFieldDesc_isValid(self.as_ptr()) }
+        self.validate().is_ok() && unsafe { PxHeightFieldDesc_isValid(self.as_ptr()) }
     }
 
     /// Set the descriptor to its default values.
@@ -314,15 +1044,18 @@ impl PxHeightFieldDesc {
         unsafe {
             PxHeightFieldDesc_setToDefault_mut(self.as_mut_ptr());
         }
+        self.nb_samples = None;
         self
     }
 }
 
-pub struct PxBVHDesc {
-    pub obj: physx_sys::PxBVHDesc,
+/// A bounding volume hierarchy descriptor.  The bounds are borrowed for `'a`.
+pub struct PxBVHDesc<'a> {
+    pub(crate) obj: physx_sys::PxBVHDesc,
+    phantom_data: PhantomData<&'a ()>,
 }
 
-unsafe impl Class<physx_sys::PxBVHDesc> for PxBVHDesc {
+unsafe impl Class<physx_sys::PxBVHDesc> for PxBVHDesc<'_> {
     fn as_ptr(&self) -> *const physx_sys::PxBVHDesc {
         &self.obj
     }
@@ -332,25 +1065,51 @@ unsafe impl Class<physx_sys::PxBVHDesc> for PxBVHDesc {
     }
 }
 
-impl Default for PxBVHDesc {
+impl Default for PxBVHDesc<'_> {
     fn default() -> Self {
         Self::new()
     }
 }
 
-impl PxBVHDesc {
+impl<'a> PxBVHDesc<'a> {
     /// Create a new BVH structure descriptor.
     pub fn new() -> Self {
         unsafe {
             Self {
                 obj: PxBVHDesc_new(),
+                phantom_data: PhantomData,
             }
         }
     }
 
+    /// Set the bounds of the primitives in the BVH.
+    pub fn set_bounds(&mut self, bounds: &'a [PxBounds3]) -> &mut Self {
+        self.obj.bounds = bounded_data(bounds);
+        self
+    }
+
+    /// Set the enlargement ratio of the bounds, only used when they are given as points.
+    pub fn set_enlargement(&mut self, enlargement: f32) -> &mut Self {
+        self.obj.enlargement = enlargement;
+        self
+    }
+
+    /// Set the maximum number of primitives in each leaf of the BVH, between 1 and 16.
+    pub fn set_num_prims_per_leaf(&mut self, num_prims_per_leaf: u32) -> &mut Self {
+        self.obj.numPrimsPerLeaf = num_prims_per_leaf;
+        self
+    }
+
+    /// Set the strategy used to build the BVH.
+    pub fn set_build_strategy(&mut self, strategy: BVHBuildStrategy) -> &mut Self {
+        self.obj.buildStrategy = strategy;
+        self
+    }
+
     /// Check if the descriptor is valid.
     pub fn is_valid(&self) -> bool {
-        unsafe { PxBVHDesc_isValid(self.as_ptr()) }
+        check_bounded_data("bounds", &self.obj.bounds, size_of::<PxBounds3>()).is_ok()
+            && unsafe { PxBVHDesc_isValid(self.as_ptr()) }
     }
 
     /// Set the descriptor to its default values.
@@ -385,4 +1144,246 @@ impl PxCookingParams {
             })
         }
     }
+
+    /// Set the midphase structure built for triangle meshes.
+    pub fn set_midphase(&mut self, midphase: MidphaseDesc) -> &mut Self {
+        unsafe {
+            match midphase {
+                MidphaseDesc::Bvh33 {
+                    mesh_cooking_hint,
+                    mesh_size_performance_trade_off,
+                } => PxCookingParams_setMidphaseBVH33(
+                    self.as_mut_ptr(),
+                    mesh_cooking_hint as u32,
+                    mesh_size_performance_trade_off,
+                ),
+                MidphaseDesc::Bvh34 {
+                    num_prims_per_leaf,
+                    build_strategy,
+                    quantized,
+                } => {
+                    let desc = physx_sys::PxBVH34MidphaseDesc {
+                        numPrimsPerLeaf: num_prims_per_leaf,
+                        buildStrategy: build_strategy,
+                        quantized,
+                        structgen_pad0: [0; 3],
+                    };
+                    PxCookingParams_setMidphaseBVH34(self.as_mut_ptr(), &desc)
+                }
+            }
+        }
+        self
+    }
+
+    /// Get the midphase structure built for triangle meshes.
+    pub fn get_midphase(&self) -> MidphaseDesc {
+        unsafe {
+            if PxMidphaseDesc_getType(&self.obj.midphaseDesc) == MeshMidPhase::Bvh33 {
+                let mut mesh_cooking_hint = 0;
+                let mut mesh_size_performance_trade_off = 0.0;
+                PxCookingParams_getMidphaseBVH33(
+                    self.as_ptr(),
+                    &mut mesh_cooking_hint,
+                    &mut mesh_size_performance_trade_off,
+                );
+                MidphaseDesc::Bvh33 {
+                    mesh_cooking_hint: if mesh_cooking_hint == 0 {
+                        MeshCookingHint::SimulationPerformance
+                    } else {
+                        MeshCookingHint::CookingPerformance
+                    },
+                    mesh_size_performance_trade_off,
+                }
+            } else {
+                let mut desc = physx_sys::PxBVH34MidphaseDesc {
+                    numPrimsPerLeaf: 0,
+                    buildStrategy: BVH34BuildStrategy::Default,
+                    quantized: false,
+                    structgen_pad0: [0; 3],
+                };
+                PxCookingParams_getMidphaseBVH34(self.as_ptr(), &mut desc);
+                MidphaseDesc::Bvh34 {
+                    num_prims_per_leaf: desc.numPrimsPerLeaf,
+                    build_strategy: desc.buildStrategy,
+                    quantized: desc.quantized,
+                }
+            }
+        }
+    }
+
+    /// Set how triangle meshes are cleaned up before cooking.
+    pub fn set_mesh_preprocess_params(&mut self, flags: MeshPreprocessingFlags) -> &mut Self {
+        self.obj.meshPreprocessParams = flags;
+        self
+    }
+
+    /// Set the distance below which vertices are welded, used with
+    /// [`MeshPreprocessingFlag::WeldVertices`].
+    pub fn set_mesh_weld_tolerance(&mut self, mesh_weld_tolerance: f32) -> &mut Self {
+        self.obj.meshWeldTolerance = mesh_weld_tolerance;
+        self
+    }
+
+    /// Set the area below which triangles are rejected as zero area while computing hulls.
+    pub fn set_area_test_epsilon(&mut self, area_test_epsilon: f32) -> &mut Self {
+        self.obj.areaTestEpsilon = area_test_epsilon;
+        self
+    }
+
+    /// Set the distance from a hull plane within which points are considered on the hull.
+    pub fn set_plane_tolerance(&mut self, plane_tolerance: f32) -> &mut Self {
+        self.obj.planeTolerance = plane_tolerance;
+        self
+    }
+
+    /// Set the vertex count above which convex meshes get a gauss map for faster
+    /// collision detection.
+    pub fn set_gauss_map_limit(&mut self, gauss_map_limit: u32) -> &mut Self {
+        self.obj.gaussMapLimit = gauss_map_limit;
+        self
+    }
+
+    /// Do not keep the table mapping cooked triangles to the input triangles.
+    pub fn set_suppress_triangle_mesh_remap_table(&mut self, suppress: bool) -> &mut Self {
+        self.obj.suppressTriangleMeshRemapTable = suppress;
+        self
+    }
+
+    /// Build the triangle adjacency information of triangle meshes.
+    pub fn set_build_triangle_adjacencies(&mut self, build: bool) -> &mut Self {
+        self.obj.buildTriangleAdjacencies = build;
+        self
+    }
+
+    /// Build the data needed to use meshes with GPU rigid bodies.
+    pub fn set_build_gpu_data(&mut self, build: bool) -> &mut Self {
+        self.obj.buildGPUData = build;
+        self
+    }
+
+    /// Check if the midphase parameters are valid.
+    pub fn is_valid(&self) -> bool {
+        unsafe { PxMidphaseDesc_isValid(&self.obj.midphaseDesc) }
+    }
+}
+
+/// Whether the BVH33 midphase favours simulation or cooking speed.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum MeshCookingHint {
+    SimulationPerformance = 0,
+    CookingPerformance = 1,
+}
+
+/// The midphase structure built for triangle meshes, used to find the triangles
+/// close to a query.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum MidphaseDesc {
+    /// The structure used up to PhysX 3.3, deprecated.
+    Bvh33 {
+        mesh_cooking_hint: MeshCookingHint,
+        /// Between 0 and 1, higher values give smaller meshes at some runtime cost.
+        mesh_size_performance_trade_off: f32,
+    },
+    /// The default structure since PhysX 3.4.
+    Bvh34 {
+        /// Between 4 and 15, lower values give faster queries and larger meshes.
+        num_prims_per_leaf: u32,
+        build_strategy: BVH34BuildStrategy,
+        /// Quantized trees are smaller but slightly slower to query.
+        quantized: bool,
+    },
+}
+
+impl MidphaseDesc {
+    /// A BVH33 midphase with the default parameters.
+    pub fn bvh33() -> Self {
+        Self::Bvh33 {
+            mesh_cooking_hint: MeshCookingHint::SimulationPerformance,
+            mesh_size_performance_trade_off: 0.55,
+        }
+    }
+
+    /// A BVH34 midphase with the default parameters.
+    pub fn bvh34() -> Self {
+        Self::Bvh34 {
+            num_prims_per_leaf: 4,
+            build_strategy: BVH34BuildStrategy::Default,
+            quantized: true,
+        }
+    }
+}
+
+impl Default for MidphaseDesc {
+    fn default() -> Self {
+        Self::bvh34()
+    }
+}
+
+/// Describes the signed distance field generated for a triangle mesh, see
+/// [`PxTriangleMeshDesc::set_sdf`].  The field is always computed from the mesh while
+/// cooking, precomputed fields are not supported.
+pub struct PxSDFDesc {
+    pub(crate) obj: physx_sys::PxSDFDesc,
+}
+
+unsafe impl Class<physx_sys::PxSDFDesc> for PxSDFDesc {
+    fn as_ptr(&self) -> *const physx_sys::PxSDFDesc {
+        &self.obj
+    }
+
+    fn as_mut_ptr(&mut self) -> *mut physx_sys::PxSDFDesc {
+        &mut self.obj
+    }
+}
+
+impl PxSDFDesc {
+    /// Create a new SDF descriptor with a distance of `spacing` between samples.
+    pub fn new(spacing: f32) -> Self {
+        let mut obj = unsafe { PxSDFDesc_new() };
+        obj.spacing = spacing;
+        Self { obj }
+    }
+
+    /// Set the distance between samples.
+    pub fn set_spacing(&mut self, spacing: f32) -> &mut Self {
+        self.obj.spacing = spacing;
+        self
+    }
+
+    /// Store the field sparsely in blocks of `subgrid_size` samples along each axis, or
+    /// densely with 0.
+    pub fn set_subgrid_size(&mut self, subgrid_size: u32) -> &mut Self {
+        self.obj.subgridSize = subgrid_size;
+        self
+    }
+
+    /// Set the precision the sparse subgrid samples are stored with.
+    pub fn set_bits_per_subgrid_pixel(&mut self, bits: SdfBitsPerSubgridPixel) -> &mut Self {
+        self.obj.bitsPerSubgridPixel = bits;
+        self
+    }
+
+    /// Set the thickness of the band around the surface that is stored sparsely, relative to
+    /// the diagonal of the bounds of the field.
+    pub fn set_narrow_band_thickness(&mut self, thickness: f32) -> &mut Self {
+        self.obj.narrowBandThicknessRelativeToSdfBoundsDiagonal = thickness;
+        self
+    }
+
+    /// Limit the field to the given bounds instead of the bounds of the mesh.
+    pub fn set_sdf_bounds(&mut self, bounds: PxBounds3) -> &mut Self {
+        self.obj.sdfBounds = bounds.into();
+        self
+    }
+
+    /// Set the number of threads used to construct the field.
+    pub fn set_num_threads_for_sdf_construction(&mut self, num_threads: u32) -> &mut Self {
+        self.obj.numThreadsForSdfConstruction = num_threads;
+        self
+    }
+
+    /// Check if the descriptor is valid.
+    pub fn is_valid(&self) -> bool {
+        unsafe { PxSDFDesc_isValid(self.as_ptr()) }
+    }
 }
@@ -0,0 +1,318 @@
+use super::{
+    try_create_convex_mesh, ConvexFlags, CookingError, CookingErrorKind, CookingMessage,
+    CookingOutput, MeshDescriptorError, PxConvexMeshDesc, PxCookingParams,
+};
+
+use crate::{convex_mesh::ConvexMesh, math::PxVec3, owner::Owner, physics::Physics};
+
+/// How [`create_convex_meshes`] turns a triangle mesh into convex meshes.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub enum ConvexDecomposition {
+    /// A single hull around the whole mesh.
+    #[default]
+    Hull,
+    /// Split the mesh in two along the longest axis of its bounds, over and over,
+    /// as long as the hulls of the halves are noticeably smaller than the hull of
+    /// the whole.  Cheap and predictable, but far from optimal for meshes whose
+    /// concavities are not aligned with the axes.
+    Approximate {
+        /// The maximum number of hulls to produce.
+        max_hulls: usize,
+        /// The maximum number of times a part of the mesh is split.
+        max_depth: u32,
+        /// Only split a part if the hulls of its halves have a volume at least this
+        /// fraction smaller than its own hull, between 0 and 1.
+        min_volume_reduction: f32,
+    },
+}
+
+/// The convex polygons of a part of the mesh, triangles or triangles clipped by planes.
+type Polygons = Vec<Vec<[f32; 3]>>;
+
+/// A part of the mesh being decomposed and its hull.
+struct Part {
+    polygons: Polygons,
+    hull: Owner<ConvexMesh>,
+    volume: f32,
+    depth: u32,
+}
+
+/// Create convex meshes that approximate a triangle soup, for using render meshes
+/// as colliders of dynamic bodies.  Each hull has at most `vertex_limit` vertices,
+/// between 4 and 255, or 64 for use with GPU rigid bodies.
+///
+/// The messages PhysX reports while cooking the hulls are collected in the output.
+pub fn create_convex_meshes(
+    physics: &mut impl Physics,
+    params: &PxCookingParams,
+    points: &[PxVec3],
+    triangles: &[[u32; 3]],
+    vertex_limit: u16,
+    decomposition: ConvexDecomposition,
+) -> Result<CookingOutput<Vec<Owner<ConvexMesh>>>, CookingError> {
+    let mut polygons = Vec::with_capacity(triangles.len());
+    for (element, triangle) in triangles.iter().enumerate() {
+        let mut polygon = Vec::with_capacity(3);
+        for &index in triangle {
+            let point = points.get(index as usize).ok_or_else(|| CookingError {
+                kind: MeshDescriptorError::IndexOutOfRange {
+                    name: "triangles",
+                    element,
+                    index,
+                    limit: points.len() as u32,
+                }
+                .into(),
+                messages: Vec::new(),
+            })?;
+            polygon.push([point.x(), point.y(), point.z()]);
+        }
+        polygons.push(polygon);
+    }
+
+    let mut messages = Vec::new();
+    let root = create_part(physics, params, polygons, vertex_limit, 0, &mut messages)?;
+
+    let (max_hulls, max_depth, min_volume_reduction) = match decomposition {
+        ConvexDecomposition::Hull => {
+            return Ok(CookingOutput {
+                value: vec![root.hull],
+                messages,
+            })
+        }
+        ConvexDecomposition::Approximate {
+            max_hulls,
+            max_depth,
+            min_volume_reduction,
+        } => (max_hulls.max(1), max_depth, min_volume_reduction),
+    };
+
+    let mut done = Vec::new();
+    let mut pending = vec![root];
+    // Split the largest part first, so the hull budget goes where it helps most.
+    while let Some(part) = pending.pop() {
+        if part.depth >= max_depth || done.len() + pending.len() + 2 > max_hulls {
+            done.push(part.hull);
+            continue;
+        }
+        let (below, above) = split(&part.polygons);
+        let halves = if below.is_empty() || above.is_empty() {
+            None
+        } else {
+            let halves = create_part(
+                physics,
+                params,
+                below,
+                vertex_limit,
+                part.depth + 1,
+                &mut messages,
+            )
+            .and_then(|below| {
+                create_part(
+                    physics,
+                    params,
+                    above,
+                    vertex_limit,
+                    part.depth + 1,
+                    &mut messages,
+                )
+                .map(|above| (below, above))
+            });
+            match halves {
+                Ok(halves) => Some(halves),
+                Err(err) => {
+                    // The split is rejected, but what PhysX reported while trying it is kept.
+                    messages.extend(err.messages);
+                    None
+                }
+            }
+        };
+        match halves {
+            Some((below, above))
+                if part.volume - below.volume - above.volume
+                    >= min_volume_reduction * part.volume =>
+            {
+                pending.push(below);
+                pending.push(above);
+                pending.sort_by(|a, b| a.volume.total_cmp(&b.volume));
+            }
+            _ => done.push(part.hull),
+        }
+    }
+
+    Ok(CookingOutput {
+        value: done,
+        messages,
+    })
+}
+
+/// Cook the hull of the points of `polygons`.
+fn create_part(
+    physics: &mut impl Physics,
+    params: &PxCookingParams,
+    polygons: Polygons,
+    vertex_limit: u16,
+    depth: u32,
+    messages: &mut Vec<CookingMessage>,
+) -> Result<Part, CookingError> {
+    let mut points: Vec<[f32; 3]> = polygons.iter().flatten().copied().collect();
+    points.sort_by(|a, b| {
+        a.iter()
+            .zip(b)
+            .map(|(a, b)| a.total_cmp(b))
+            .find(|ordering| ordering.is_ne())
+            .unwrap_or(std::cmp::Ordering::Equal)
+    });
+    points.dedup();
+    if points.len() < 4 {
+        return Err(CookingError {
+            kind: CookingErrorKind::ZeroAreaTestFailed,
+            messages: Vec::new(),
+        });
+    }
+    let points: Vec<PxVec3> = points
+        .into_iter()
+        .map(|[x, y, z]| PxVec3::new(x, y, z))
+        .collect();
+
+    let mut desc = PxConvexMeshDesc::new();
+    desc.set_points(&points)
+        .set_flags(ConvexFlags::ComputeConvex)
+        .set_vertex_limit(vertex_limit);
+    let output = try_create_convex_mesh(physics, params, &desc)?;
+    messages.extend(output.messages);
+    let volume = output.value.get_mass_information().mass;
+    Ok(Part {
+        polygons,
+        hull: output.value,
+        volume,
+        depth,
+    })
+}
+
+/// Clip the polygons against the plane through the middle of their bounds, across
+/// the longest axis.
+fn split(polygons: &[Vec<[f32; 3]>]) -> (Polygons, Polygons) {
+    let mut min = [f32::MAX; 3];
+    let mut max = [f32::MIN; 3];
+    for point in polygons.iter().flatten() {
+        for axis in 0..3 {
+            min[axis] = min[axis].min(point[axis]);
+            max[axis] = max[axis].max(point[axis]);
+        }
+    }
+    let axis = (0..3)
+        .max_by(|&a, &b| (max[a] - min[a]).total_cmp(&(max[b] - min[b])))
+        .unwrap_or(0);
+    let plane = (min[axis] + max[axis]) * 0.5;
+
+    let mut below = Vec::new();
+    let mut above = Vec::new();
+    for polygon in polygons {
+        let clipped_below = clip(polygon, |point| plane - point[axis], axis, plane);
+        let clipped_above = clip(polygon, |point| point[axis] - plane, axis, plane);
+        if clipped_below.len() >= 3 {
+            below.push(clipped_below);
+        }
+        if clipped_above.len() >= 3 {
+            above.push(clipped_above);
+        }
+    }
+    (below, above)
+}
+
+/// Keep the part of a convex polygon where `distance` is not negative.
+fn clip(
+    polygon: &[[f32; 3]],
+    distance: impl Fn(&[f32; 3]) -> f32,
+    axis: usize,
+    plane: f32,
+) -> Vec<[f32; 3]> {
+    let mut clipped = Vec::with_capacity(polygon.len() + 1);
+    for (index, current) in polygon.iter().enumerate() {
+        let next = &polygon[(index + 1) % polygon.len()];
+        let (current_distance, next_distance) = (distance(current), distance(next));
+        if current_distance >= 0.0 {
+            clipped.push(*current);
+        }
+        if (current_distance >= 0.0) != (next_distance >= 0.0) {
+            let t = current_distance / (current_distance - next_distance);
+            let mut intersection = [0.0; 3];
+            for (i, value) in intersection.iter_mut().enumerate() {
+                *value = current[i] + (next[i] - current[i]) * t;
+            }
+            // Snap to the plane so both halves share the exact same points.
+            intersection[axis] = plane;
+            clipped.push(intersection);
+        }
+    }
+    clipped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{clip, split};
+
+    #[test]
+    fn clip_keeps_the_positive_side() {
+        let triangle = [[0.0, 0.0, 0.0], [1.0, 0.0, 0.0], [0.0, 1.0, 0.0]];
+        let clipped = clip(&triangle, |point| 0.5 - point[0], 0, 0.5);
+        assert_eq!(
+            clipped,
+            vec![
+                [0.0, 0.0, 0.0],
+                [0.5, 0.0, 0.0],
+                [0.5, 0.5, 0.0],
+                [0.0, 1.0, 0.0]
+            ]
+        );
+    }
+
+    #[test]
+    fn clip_drops_polygons_on_the_negative_side() {
+        let triangle = [[2.0, 0.0, 0.0], [3.0, 0.0, 0.0], [2.0, 1.0, 0.0]];
+        assert!(clip(&triangle, |point| 1.0 - point[0], 0, 1.0).is_empty());
+        assert_eq!(clip(&triangle, |point| point[0] - 1.0, 0, 1.0), triangle);
+    }
+
+    #[test]
+    fn split_cuts_across_the_longest_axis() {
+        let polygons = vec![
+            vec![[0.0, 0.0, 0.0], [4.0, 0.0, 0.0], [4.0, 1.0, 0.0]],
+            vec![[0.0, 0.0, 0.0], [4.0, 1.0, 0.0], [0.0, 1.0, 0.0]],
+        ];
+        let (below, above) = split(&polygons);
+        assert_eq!(below.len(), 2);
+        assert_eq!(above.len(), 2);
+        assert!(below.iter().flatten().all(|point| point[0] <= 2.0));
+        assert!(above.iter().flatten().all(|point| point[0] >= 2.0));
+
+        // Both halves share the points on the plane exactly.
+        let on_plane = |polygons: &[Vec<[f32; 3]>]| {
+            let mut points: Vec<[f32; 3]> = polygons
+                .iter()
+                .flatten()
+                .copied()
+                .filter(|point| point[0] == 2.0)
+                .collect();
+            points.sort_by(|a, b| a[1].total_cmp(&b[1]));
+            points.dedup();
+            points
+        };
+        assert_eq!(on_plane(&below), on_plane(&above));
+        assert_eq!(
+            on_plane(&below),
+            vec![[2.0, 0.0, 0.0], [2.0, 0.5, 0.0], [2.0, 1.0, 0.0]]
+        );
+    }
+
+    #[test]
+    fn split_keeps_polygons_that_do_not_cross_the_plane_whole() {
+        let polygons = vec![
+            vec![[0.0, 0.0, 0.0], [1.0, 0.0, 0.0], [0.0, 0.0, 1.0]],
+            vec![[3.0, 0.0, 0.0], [4.0, 0.0, 0.0], [3.0, 0.0, 1.0]],
+        ];
+        let (below, above) = split(&polygons);
+        assert_eq!(below, vec![polygons[0].clone()]);
+        assert_eq!(above, vec![polygons[1].clone()]);
+    }
+}
@@ -0,0 +1,193 @@
+use super::MeshDescriptorError;
+
+use std::{
+    cell::RefCell,
+    ffi::{c_void, CStr},
+    fmt,
+    ptr::null_mut,
+    sync::{Mutex, PoisonError},
+};
+
+use thiserror::Error;
+
+#[rustfmt::skip]
+use physx_sys::{
+    create_error_callback,
+    phys_PxGetFoundation,
+    PxErrorCallback,
+    PxErrorCode,
+    PxFoundation,
+    PxFoundation_registerErrorCallback_mut,
+};
+
+/// A message PhysX reported through the error callback while cooking.
+#[derive(Clone, Debug)]
+pub struct CookingMessage {
+    pub code: PxErrorCode,
+    pub message: String,
+    pub file: String,
+    pub line: u32,
+}
+
+impl CookingMessage {
+    /// Returns true if the message is informational or a warning rather than an error.
+    pub fn is_warning(&self) -> bool {
+        matches!(
+            self.code,
+            PxErrorCode::DebugInfo | PxErrorCode::DebugWarning | PxErrorCode::PerfWarning
+        )
+    }
+}
+
+impl fmt::Display for CookingMessage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?}: {}", self.code, self.message.trim_end())
+    }
+}
+
+/// Which check failed when cooking or validating a mesh.
+#[derive(Error, Debug)]
+pub enum CookingErrorKind {
+    #[error("the descriptor is invalid: {0}")]
+    InvalidDescriptor(#[from] MeshDescriptorError),
+
+    #[error("the descriptor or cooking parameters were rejected by PhysX")]
+    RejectedDescriptor,
+
+    #[error("the mesh has duplicate vertices or degenerate triangles")]
+    DuplicateVertices,
+
+    #[error("the mesh has triangles that are too large for a stable simulation")]
+    LargeTriangles,
+
+    #[error("the hull has a polygon with zero area")]
+    ZeroAreaTestFailed,
+
+    #[error("the hull reached the polygon limit")]
+    PolygonsLimitReached,
+
+    #[error("the physics has no insertion callback")]
+    NoInsertionCallback,
+
+    #[error("no foundation has been created")]
+    NoFoundation,
+
+    #[error("cooking failed")]
+    Failure,
+}
+
+/// The error of a cooking call, with the messages PhysX reported during the call.
+#[derive(Error, Debug)]
+#[error("{kind}")]
+pub struct CookingError {
+    pub kind: CookingErrorKind,
+    pub messages: Vec<CookingMessage>,
+}
+
+impl CookingError {
+    /// The failed check followed by every reported message, for showing as
+    /// warnings when importing an asset.
+    pub fn into_warnings(self) -> Vec<String> {
+        std::iter::once(self.kind.to_string())
+            .chain(self.messages.iter().map(ToString::to_string))
+            .collect()
+    }
+}
+
+/// The result of a successful cooking call, with the messages PhysX reported during the call.
+#[derive(Debug)]
+pub struct CookingOutput<T> {
+    pub value: T,
+    pub messages: Vec<CookingMessage>,
+}
+
+impl<T> CookingOutput<T> {
+    /// The reported messages, for showing as warnings when importing an asset.
+    pub fn warnings(&self) -> Vec<String> {
+        self.messages.iter().map(ToString::to_string).collect()
+    }
+}
+
+/// The warning PhysX reports when cooking a triangle mesh whose triangles are large
+/// compared to the tolerance scale, see GuCookingTriangleMesh.cpp:202.
+pub(super) const LARGE_TRIANGLES_MESSAGE: &str = "triangles are too big";
+
+/// Run a cooking call and attach the messages it reported to its result.  Messages are
+/// only captured from foundations created with [`Foundation`](crate::foundation::Foundation).
+pub(super) fn capture_messages<T>(
+    cook: impl FnOnce() -> Result<T, CookingErrorKind>,
+) -> Result<CookingOutput<T>, CookingError> {
+    if unsafe { phys_PxGetFoundation() }.is_null() {
+        return Err(CookingError {
+            kind: CookingErrorKind::NoFoundation,
+            messages: Vec::new(),
+        });
+    }
+    let previous = CAPTURED.with(|captured| captured.replace(Some(Vec::new())));
+    let result = cook();
+    let messages = CAPTURED
+        .with(|captured| captured.replace(previous))
+        .unwrap_or_default();
+    match result {
+        Ok(value) => Ok(CookingOutput { value, messages }),
+        Err(kind) => Err(CookingError { kind, messages }),
+    }
+}
+
+thread_local! {
+    /// The messages reported on this thread during the current cooking call, if any.
+    static CAPTURED: RefCell<Option<Vec<CookingMessage>>> = const { RefCell::new(None) };
+}
+
+/// The capturing error callback, created the first time a foundation is created.
+struct Listener(*mut PxErrorCallback);
+
+// The pointer is only used while holding the lock.
+unsafe impl Send for Listener {}
+
+static LISTENER: Mutex<Listener> = Mutex::new(Listener(null_mut()));
+
+/// Register the capturing error callback with a foundation the crate just created, before
+/// it can be used from other threads.
+///
+/// The callback is never deregistered: the foundation walks its listeners under a different
+/// lock than the one guarding registration, so removing it could race with another thread
+/// reporting an error.
+pub(crate) fn register_listener(foundation: *mut PxFoundation) {
+    let mut listener = LISTENER.lock().unwrap_or_else(PoisonError::into_inner);
+    unsafe {
+        if listener.0.is_null() {
+            listener.0 = create_error_callback(on_message, null_mut());
+        }
+        PxFoundation_registerErrorCallback_mut(foundation, listener.0);
+    }
+}
+
+/// Keeps the message if the reporting thread is in a cooking call.
+unsafe extern "C" fn on_message(
+    code: PxErrorCode,
+    message: *const i8,
+    file: *const i8,
+    line: u32,
+    _userdata: *const c_void,
+) {
+    // Unwinding out of the callback would abort, so skip the message rather than panic.
+    let _ = CAPTURED.try_with(|captured| {
+        if let Ok(Some(messages)) = captured.try_borrow_mut().as_deref_mut() {
+            let (message, file) = unsafe {
+                (
+                    CStr::from_ptr(message.cast())
+                        .to_string_lossy()
+                        .into_owned(),
+                    CStr::from_ptr(file.cast()).to_string_lossy().into_owned(),
+                )
+            };
+            messages.push(CookingMessage {
+                code,
+                message,
+                file,
+                line,
+            });
+        }
+    });
+}
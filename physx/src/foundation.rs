@@ -121,14 +121,17 @@ pub trait Foundation: Class<physx_sys::PxFoundation> + Sized {
         error_callback: *mut PxErrorCallback,
     ) -> Option<Owner<Self>> {
         unsafe {
-            Owner::from_raw(
-                phys_PxCreateFoundation(
-                    crate::physics::PX_PHYSICS_VERSION,
-                    allocator.into_px(),
-                    error_callback,
-                )
-                .cast::<Self>(),
-            )
+            let foundation = phys_PxCreateFoundation(
+                crate::physics::PX_PHYSICS_VERSION,
+                allocator.into_px(),
+                error_callback,
+            );
+            if !foundation.is_null() {
+                // Lets the `try_*` and `check_*` cooking functions capture the messages
+                // reported while cooking.
+                crate::cooking::register_listener(foundation);
+            }
+            Owner::from_raw(foundation.cast::<Self>())
         }
     }
 
@@ -6,7 +6,12 @@
 #![allow(clippy::new_ret_no_self)]
 
 use crate::{
-    convex_mesh::ConvexMesh, height_field::HeightField, traits::Class, triangle_mesh::TriangleMesh,
+    convex_mesh::ConvexMesh,
+    height_field::{HeightField, HeightFieldMaterial},
+    math::{PxBounds3, PxTransform, PxVec3},
+    scene::HitFlags,
+    traits::Class,
+    triangle_mesh::TriangleMesh,
 };
 
 #[rustfmt::skip]
@@ -19,6 +24,7 @@ pub use physx_sys::{
     PxGeometry,
     PxGeometryType as GeometryType,
     PxHeightFieldGeometry,
+    PxMat33,
     PxMeshGeometryFlag as MeshGeometryFlag,
     PxMeshGeometryFlags as MeshGeometryFlags,
     PxPlaneGeometry,
@@ -34,20 +40,45 @@ use physx_sys::{
     PxCapsuleGeometry_new,
     PxConvexMeshGeometry_isValid,
     PxConvexMeshGeometry_new,
+    PxGeomRaycastHit,
+    PxGeomSweepHit,
+    PxGeometryQueryFlags,
+    PxGeometryQuery_computeGeomBounds,
+    PxGeometryQuery_computePenetration,
+    PxGeometryQuery_overlap,
+    PxGeometryQuery_pointDistance,
+    PxGeometryQuery_raycast,
+    PxGeometryQuery_sweep,
     PxGeometry_getType,
     PxHeightFieldGeometry_isValid,
     PxHeightFieldGeometry_new,
+    PxMeshQuery_findOverlapHeightField,
+    PxMeshQuery_findOverlapTriangleMesh,
+    PxMeshQuery_getTriangle,
+    PxMeshQuery_getTriangle_1,
+    PxMeshQuery_sweep,
     PxMeshScale,
     PxPlaneGeometry_isValid,
     PxPlaneGeometry_new,
     PxSphereGeometry_isValid,
     PxSphereGeometry_new,
+    PxTriangle,
     PxTriangleMeshGeometry_isValid,
     PxTriangleMeshGeometry_new,
 };
 
 use std::ptr;
 
+/// The mass properties of a mesh, computed for a density of 1.
+#[derive(Copy, Clone)]
+pub struct MassInformation {
+    pub mass: f32,
+    /// The inertia tensor around the center of mass, in mesh local space.
+    pub local_inertia: PxMat33,
+    /// The center of mass, in mesh local space.
+    pub local_center_of_mass: PxVec3,
+}
+
 impl<T> Geometry for T where T: Class<PxGeometry> {}
 pub trait Geometry: Class<PxGeometry> {
     fn get_type(&self) -> GeometryType {
@@ -160,6 +191,65 @@ pub trait TriangleMeshGeometry: Class<PxTriangleMeshGeometry> + Geometry {
     fn is_valid(&self) -> bool {
         unsafe { PxTriangleMeshGeometry_isValid(self.as_ptr()) }
     }
+
+    /// Find the indices of the triangles of the mesh at `pose` that touch a geometry at
+    /// `geometry_pose`.  Triangle meshes can not be tested against other triangle meshes.
+    fn find_overlapping_triangles(
+        &self,
+        pose: &PxTransform,
+        geometry: &impl Geometry,
+        geometry_pose: &PxTransform,
+    ) -> Vec<u32> {
+        find_overlaps(|results, max_results, start_index, overflow| unsafe {
+            PxMeshQuery_findOverlapTriangleMesh(
+                geometry.as_ptr(),
+                geometry_pose.as_ptr(),
+                self.as_ptr(),
+                pose.as_ptr(),
+                results,
+                max_results,
+                start_index,
+                overflow,
+                PxGeometryQueryFlags::SimdGuard,
+            )
+        })
+    }
+
+    /// Get a triangle of the mesh at `pose` in world space, or None if the index is out
+    /// of range.
+    fn get_triangle(&self, pose: &PxTransform, triangle_index: u32) -> Option<MeshTriangle> {
+        let geometry = unsafe { &*Class::<PxTriangleMeshGeometry>::as_ptr(self) };
+        let mesh = unsafe { (geometry.triangleMesh as *const TriangleMesh).as_ref()? };
+        if triangle_index >= mesh.get_nb_triangles() {
+            return None;
+        }
+        let mut triangle = MeshTriangle::default();
+        unsafe {
+            PxMeshQuery_getTriangle(
+                self.as_ptr(),
+                pose.as_ptr(),
+                triangle_index,
+                (&mut triangle.vertices as *mut [PxVec3; 3]).cast::<PxTriangle>(),
+                triangle.vertex_indices.as_mut_ptr(),
+                triangle.adjacency_indices.as_mut_ptr(),
+            );
+        }
+        Some(triangle)
+    }
+
+    /// Get the triangles of the mesh at `pose` touching a geometry at `geometry_pose`,
+    /// in world space.
+    fn get_overlapping_triangles(
+        &self,
+        pose: &PxTransform,
+        geometry: &impl Geometry,
+        geometry_pose: &PxTransform,
+    ) -> Vec<MeshTriangle> {
+        self.find_overlapping_triangles(pose, geometry, geometry_pose)
+            .into_iter()
+            .filter_map(|triangle_index| self.get_triangle(pose, triangle_index))
+            .collect()
+    }
 }
 
 impl<T> HeightFieldGeometry for T where T: Class<PxHeightFieldGeometry> + Geometry {}
@@ -191,4 +281,424 @@ pub trait HeightFieldGeometry: Class<PxHeightFieldGeometry> + Geometry {
     fn is_valid(&self) -> bool {
         unsafe { PxHeightFieldGeometry_isValid(self.as_ptr()) }
     }
+
+    /// Find the indices of the triangles of the height field at `pose` that touch a geometry
+    /// at `geometry_pose`.
+    fn find_overlapping_triangles(
+        &self,
+        pose: &PxTransform,
+        geometry: &impl Geometry,
+        geometry_pose: &PxTransform,
+    ) -> Vec<u32> {
+        find_overlaps(|results, max_results, start_index, overflow| unsafe {
+            PxMeshQuery_findOverlapHeightField(
+                geometry.as_ptr(),
+                geometry_pose.as_ptr(),
+                self.as_ptr(),
+                pose.as_ptr(),
+                results,
+                max_results,
+                start_index,
+                overflow,
+                PxGeometryQueryFlags::SimdGuard,
+            )
+        })
+    }
+
+    /// Get a triangle of the height field at `pose` in world space, or None if the index
+    /// is out of range.  Each cell has two triangles, triangle `2 * (row * columns + column)`
+    /// and the one after it.
+    fn get_triangle(&self, pose: &PxTransform, triangle_index: u32) -> Option<MeshTriangle> {
+        let geometry = unsafe { &*Class::<PxHeightFieldGeometry>::as_ptr(self) };
+        let height_field = unsafe { (geometry.heightField as *const HeightField).as_ref()? };
+        if !height_field.has_cell_triangle(triangle_index) {
+            return None;
+        }
+        let mut triangle = MeshTriangle::default();
+        unsafe {
+            PxMeshQuery_getTriangle_1(
+                self.as_ptr(),
+                pose.as_ptr(),
+                triangle_index,
+                (&mut triangle.vertices as *mut [PxVec3; 3]).cast::<PxTriangle>(),
+                triangle.vertex_indices.as_mut_ptr(),
+                triangle.adjacency_indices.as_mut_ptr(),
+            );
+        }
+        Some(triangle)
+    }
+
+    /// Get the triangles of the height field at `pose` touching a geometry at
+    /// `geometry_pose`, in world space.
+    fn get_overlapping_triangles(
+        &self,
+        pose: &PxTransform,
+        geometry: &impl Geometry,
+        geometry_pose: &PxTransform,
+    ) -> Vec<MeshTriangle> {
+        self.find_overlapping_triangles(pose, geometry, geometry_pose)
+            .into_iter()
+            .filter_map(|triangle_index| self.get_triangle(pose, triangle_index))
+            .collect()
+    }
+
+    /// Get the height of the surface under or over a world space point, in the local space
+    /// of the height field at `pose`.  Returns None if the point is not over the height field
+    /// or is over a hole.
+    fn get_height_at(&self, pose: &PxTransform, point: &PxVec3) -> Option<f32> {
+        let (geometry, height_field, x, z, _) = locate_on_height_field(self, pose, point)?;
+        Some(height_field.get_height(x, z) * geometry.heightScale)
+    }
+
+    /// Get the world space normal of the surface under or over a world space point on the
+    /// height field at `pose`.  Returns None if the point is not over the height field or is
+    /// over a hole.
+    fn get_normal_at(&self, pose: &PxTransform, point: &PxVec3) -> Option<PxVec3> {
+        let (geometry, height_field, _, _, triangle_index) =
+            locate_on_height_field(self, pose, point)?;
+        let normal = height_field.get_triangle_normal(triangle_index)?;
+        let normal = PxVec3::new(
+            normal.x() / geometry.rowScale,
+            normal.y() / geometry.heightScale,
+            normal.z() / geometry.columnScale,
+        )
+        .get_normalized();
+        Some(pose.rotate(&normal))
+    }
+}
+
+/// A triangle of a mesh or height field, see [`TriangleMeshGeometry::get_triangle`].
+#[derive(Copy, Clone, Default)]
+pub struct MeshTriangle {
+    /// The vertices in world space.
+    pub vertices: [PxVec3; 3],
+    /// The indices of the vertices in the mesh.
+    pub vertex_indices: [u32; 3],
+    /// The triangles sharing the edges from vertex 0 to 1, 1 to 2 and 2 to 0, or
+    /// `u32::MAX` if there is none.  Only available for meshes cooked with adjacency
+    /// information and for height fields.
+    pub adjacency_indices: [u32; 3],
+}
+
+/// Collect the results of a `PxMeshQuery_findOverlap*` call, calling it again
+/// while the buffer overflows.
+fn find_overlaps(mut query: impl FnMut(*mut u32, u32, u32, &mut bool) -> u32) -> Vec<u32> {
+    const BATCH_SIZE: usize = 256;
+    let mut results = Vec::new();
+    loop {
+        let start = results.len();
+        results.resize(start + BATCH_SIZE, 0);
+        let mut overflow = false;
+        let count = query(
+            results[start..].as_mut_ptr(),
+            BATCH_SIZE as u32,
+            start as u32,
+            &mut overflow,
+        );
+        results.truncate(start + count as usize);
+        if !overflow || count == 0 {
+            return results;
+        }
+    }
+}
+
+/// Find the sample space position and triangle under a world space point.
+fn locate_on_height_field<'a, G: HeightFieldGeometry + ?Sized>(
+    geometry: &'a G,
+    pose: &PxTransform,
+    point: &PxVec3,
+) -> Option<(&'a PxHeightFieldGeometry, &'a HeightField, f32, f32, u32)> {
+    let geometry = unsafe { &*Class::<PxHeightFieldGeometry>::as_ptr(geometry) };
+    let height_field = unsafe { (geometry.heightField as *const HeightField).as_ref()? };
+    let local = pose.transform_vector_inv(point);
+    let (x, z) = (
+        local.x() / geometry.rowScale,
+        local.z() / geometry.columnScale,
+    );
+    let triangle_index = height_field.get_triangle_index(x, z)?;
+    let material_index = height_field.get_triangle_material_index(triangle_index)?;
+    if material_index == HeightFieldMaterial::Hole as u16 {
+        return None;
+    }
+    Some((geometry, height_field, x, z, triangle_index))
+}
+
+/// A hit reported by [`raycast`].
+#[derive(Copy, Clone)]
+pub struct GeometryRaycastHit {
+    /// Triangle index for mesh and height field hits, only valid if `HitFlag::FaceIndex` is set.
+    pub face_index: u32,
+    /// Which fields of this hit are valid.
+    pub flags: HitFlags,
+    /// World space position of the hit, only valid if `HitFlag::Position` is set.
+    pub position: PxVec3,
+    /// World space normal of the hit, only valid if `HitFlag::Normal` is set.
+    pub normal: PxVec3,
+    /// Distance from the ray origin to the hit.
+    pub distance: f32,
+    /// Barycentric u coordinate of the hit, only valid if `HitFlag::Uv` is set.
+    pub u: f32,
+    /// Barycentric v coordinate of the hit, only valid if `HitFlag::Uv` is set.
+    pub v: f32,
+}
+
+impl From<PxGeomRaycastHit> for GeometryRaycastHit {
+    fn from(hit: PxGeomRaycastHit) -> Self {
+        Self {
+            face_index: hit.faceIndex,
+            flags: hit.flags,
+            position: hit.position.into(),
+            normal: hit.normal.into(),
+            distance: hit.distance,
+            u: hit.u,
+            v: hit.v,
+        }
+    }
+}
+
+/// A hit reported by [`sweep`].
+#[derive(Copy, Clone)]
+pub struct GeometrySweepHit {
+    /// Triangle index for mesh and height field hits, only valid if `HitFlag::FaceIndex` is set.
+    pub face_index: u32,
+    /// Which fields of this hit are valid.
+    pub flags: HitFlags,
+    /// World space position of the hit, only valid if `HitFlag::Position` is set.
+    pub position: PxVec3,
+    /// World space normal of the hit, only valid if `HitFlag::Normal` is set.
+    pub normal: PxVec3,
+    /// Distance along the sweep direction to the hit.
+    pub distance: f32,
+}
+
+impl From<PxGeomSweepHit> for GeometrySweepHit {
+    fn from(hit: PxGeomSweepHit) -> Self {
+        Self {
+            face_index: hit.faceIndex,
+            flags: hit.flags,
+            position: hit.position.into(),
+            normal: hit.normal.into(),
+            distance: hit.distance,
+        }
+    }
+}
+
+/// The minimum translational distance between two overlapping geometries, see
+/// [`compute_penetration`].
+#[derive(Copy, Clone)]
+pub struct Penetration {
+    /// The direction to move the first geometry in to separate them.
+    pub direction: PxVec3,
+    /// How far the first geometry has to move to separate them.
+    pub depth: f32,
+}
+
+/// The point of a geometry closest to another point, see [`point_distance`].
+#[derive(Copy, Clone)]
+pub struct PointDistance {
+    /// The squared distance to the geometry, 0 if the point is inside it.
+    pub distance_squared: f32,
+    /// The closest point on the geometry.
+    pub closest_point: PxVec3,
+    /// The index of the closest triangle for meshes.
+    pub closest_index: u32,
+}
+
+/// Raycast against a geometry at `pose`, returning at most `max_hits` hits.  Only
+/// meshes and height fields report more than one hit, and only with
+/// [`HitFlag::MeshMultiple`](crate::scene::HitFlag::MeshMultiple) set.
+pub fn raycast(
+    origin: &PxVec3,
+    unit_dir: &PxVec3,
+    geometry: &impl Geometry,
+    pose: &PxTransform,
+    max_distance: f32,
+    hit_flags: HitFlags,
+    max_hits: u32,
+) -> Vec<GeometryRaycastHit> {
+    if max_hits == 0 {
+        return Vec::new();
+    }
+    // Safety: the hits are plain old data.
+    let mut hits: Vec<PxGeomRaycastHit> = vec![unsafe { std::mem::zeroed() }; max_hits as usize];
+    let count = unsafe {
+        PxGeometryQuery_raycast(
+            origin.as_ptr(),
+            unit_dir.as_ptr(),
+            geometry.as_ptr(),
+            pose.as_ptr(),
+            max_distance,
+            hit_flags,
+            max_hits,
+            hits.as_mut_ptr(),
+            std::mem::size_of::<PxGeomRaycastHit>() as u32,
+            PxGeometryQueryFlags::SimdGuard,
+            ptr::null_mut(),
+        )
+    };
+    hits.truncate(count as usize);
+    hits.into_iter().map(GeometryRaycastHit::from).collect()
+}
+
+/// Returns true if two geometries overlap.
+pub fn overlap(
+    geometry0: &impl Geometry,
+    pose0: &PxTransform,
+    geometry1: &impl Geometry,
+    pose1: &PxTransform,
+) -> bool {
+    unsafe {
+        PxGeometryQuery_overlap(
+            geometry0.as_ptr(),
+            pose0.as_ptr(),
+            geometry1.as_ptr(),
+            pose1.as_ptr(),
+            PxGeometryQueryFlags::SimdGuard,
+            ptr::null_mut(),
+        )
+    }
+}
+
+/// Sweep `geometry0` from `pose0` along `unit_dir` against `geometry1` at `pose1`.  The swept
+/// geometry must be a sphere, capsule, box or convex mesh.
+#[allow(clippy::too_many_arguments)]
+pub fn sweep(
+    unit_dir: &PxVec3,
+    max_distance: f32,
+    geometry0: &impl Geometry,
+    pose0: &PxTransform,
+    geometry1: &impl Geometry,
+    pose1: &PxTransform,
+    hit_flags: HitFlags,
+    inflation: f32,
+) -> Option<GeometrySweepHit> {
+    // Safety: the hit is plain old data.
+    let mut hit: PxGeomSweepHit = unsafe { std::mem::zeroed() };
+    unsafe {
+        PxGeometryQuery_sweep(
+            unit_dir.as_ptr(),
+            max_distance,
+            geometry0.as_ptr(),
+            pose0.as_ptr(),
+            geometry1.as_ptr(),
+            pose1.as_ptr(),
+            &mut hit,
+            hit_flags,
+            inflation,
+            PxGeometryQueryFlags::SimdGuard,
+            ptr::null_mut(),
+        )
+    }
+    .then(|| hit.into())
+}
+
+/// Compute the minimum translational distance to separate two geometries, or None if they
+/// do not overlap.
+pub fn compute_penetration(
+    geometry0: &impl Geometry,
+    pose0: &PxTransform,
+    geometry1: &impl Geometry,
+    pose1: &PxTransform,
+) -> Option<Penetration> {
+    let mut direction = PxVec3::default();
+    let mut depth = 0.0;
+    unsafe {
+        PxGeometryQuery_computePenetration(
+            direction.as_mut_ptr(),
+            &mut depth,
+            geometry0.as_ptr(),
+            pose0.as_ptr(),
+            geometry1.as_ptr(),
+            pose1.as_ptr(),
+            PxGeometryQueryFlags::SimdGuard,
+        )
+    }
+    .then_some(Penetration { direction, depth })
+}
+
+/// Find the point of a geometry closest to `point`.  Returns None for unsupported geometries,
+/// only boxes, spheres, capsules, convex meshes and triangle meshes with a BVH34 midphase
+/// are supported.
+pub fn point_distance(
+    point: &PxVec3,
+    geometry: &impl Geometry,
+    pose: &PxTransform,
+) -> Option<PointDistance> {
+    let mut closest_point = PxVec3::default();
+    let mut closest_index = 0;
+    let distance_squared = unsafe {
+        PxGeometryQuery_pointDistance(
+            point.as_ptr(),
+            geometry.as_ptr(),
+            pose.as_ptr(),
+            closest_point.as_mut_ptr(),
+            &mut closest_index,
+            PxGeometryQueryFlags::SimdGuard,
+        )
+    };
+    (distance_squared >= 0.0).then_some(PointDistance {
+        distance_squared,
+        closest_point,
+        closest_index,
+    })
+}
+
+/// Compute the world space bounds of a geometry at `pose`.  The extents are grown by
+/// `offset` and then scaled by `inflation`, PhysX uses 1.01 for the bounds of shapes.
+pub fn compute_bounds(
+    geometry: &impl Geometry,
+    pose: &PxTransform,
+    offset: f32,
+    inflation: f32,
+) -> PxBounds3 {
+    let mut bounds = PxBounds3::default();
+    unsafe {
+        PxGeometryQuery_computeGeomBounds(
+            bounds.as_mut_ptr(),
+            geometry.as_ptr(),
+            pose.as_ptr(),
+            offset,
+            inflation,
+            PxGeometryQueryFlags::SimdGuard,
+        );
+    }
+    bounds
+}
+
+/// Sweep a geometry from `pose` along `unit_dir` against a set of world space triangles.
+/// The hit's face index is the index of the triangle in `triangles`.  Pass the index of the
+/// triangle hit by a previous sweep as `cached_index` to test it first.
+#[allow(clippy::too_many_arguments)]
+pub fn sweep_triangles(
+    unit_dir: &PxVec3,
+    max_distance: f32,
+    geometry: &impl Geometry,
+    pose: &PxTransform,
+    triangles: &[[PxVec3; 3]],
+    hit_flags: HitFlags,
+    cached_index: Option<u32>,
+    inflation: f32,
+    double_sided: bool,
+) -> Option<GeometrySweepHit> {
+    // Safety: the hit is plain old data.
+    let mut hit: PxGeomSweepHit = unsafe { std::mem::zeroed() };
+    unsafe {
+        PxMeshQuery_sweep(
+            unit_dir.as_ptr(),
+            max_distance,
+            geometry.as_ptr(),
+            pose.as_ptr(),
+            triangles.len() as u32,
+            triangles.as_ptr().cast::<PxTriangle>(),
+            &mut hit,
+            hit_flags,
+            cached_index
+                .as_ref()
+                .map_or(ptr::null(), |index| index as *const u32),
+            inflation,
+            double_sided,
+            PxGeometryQueryFlags::SimdGuard,
+        )
+    }
+    .then(|| hit.into())
 }
@@ -2,12 +2,99 @@
 // Copyright © 2019, Embark Studios, all rights reserved.
 // Created: 11 April 2019
 
-use crate::{owner::Owner, traits::Class};
-use physx_sys::PxHeightField_release_mut;
+use crate::{cooking::PxHeightFieldDesc, math::PxVec3, owner::Owner, traits::Class};
+
+use thiserror::Error;
+
+#[rustfmt::skip]
+use physx_sys::{
+    PxBitAndByte,
+    PxHeightField_getConvexEdgeThreshold,
+    PxHeightField_getFlags,
+    PxHeightField_getFormat,
+    PxHeightField_getHeight,
+    PxHeightField_getNbColumns,
+    PxHeightField_getNbRows,
+    PxHeightField_getSample,
+    PxHeightField_getTimestamp,
+    PxHeightField_getTriangleMaterialIndex,
+    PxHeightField_getTriangleNormal,
+    PxHeightField_modifySamples_mut,
+    PxHeightField_release_mut,
+    PxHeightField_saveCells,
+};
+
+#[rustfmt::skip]
+pub use physx_sys::{
+    PxHeightFieldFlag as HeightFieldFlag,
+    PxHeightFieldFlags as HeightFieldFlags,
+    PxHeightFieldFormat as HeightFieldFormat,
+    PxHeightFieldMaterial as HeightFieldMaterial,
+};
 
 pub const HEIGHT_SCALE: f32 = 1.0;
 pub const XZ_SCALE: f32 = 100.0;
 
+const BIT: u8 = 0x80;
+
+/// A sample of a height field, the unpacked form of `PxHeightFieldSample`.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct HeightFieldSample {
+    pub height: i16,
+    /// The material of the first triangle of the cell, 7 bits.  [`HeightFieldMaterial::Hole`]
+    /// makes the triangle a hole.
+    pub material_index0: u8,
+    /// The material of the second triangle of the cell, 7 bits.
+    pub material_index1: u8,
+    /// Which diagonal the cell is split along.  If set, the cell is split from
+    /// this sample to the sample diagonally across from it.
+    pub tess_flag: bool,
+}
+
+impl From<physx_sys::PxHeightFieldSample> for HeightFieldSample {
+    fn from(sample: physx_sys::PxHeightFieldSample) -> Self {
+        let index0 = sample.materialIndex0.structgen_pad0[0];
+        let index1 = sample.materialIndex1.structgen_pad0[0];
+        Self {
+            height: sample.height,
+            material_index0: index0 & !BIT,
+            material_index1: index1 & !BIT,
+            tess_flag: index0 & BIT != 0,
+        }
+    }
+}
+
+impl From<HeightFieldSample> for physx_sys::PxHeightFieldSample {
+    fn from(sample: HeightFieldSample) -> Self {
+        let tess_bit = if sample.tess_flag { BIT } else { 0 };
+        Self {
+            height: sample.height,
+            materialIndex0: PxBitAndByte {
+                structgen_pad0: [sample.material_index0 & !BIT | tess_bit],
+            },
+            materialIndex1: PxBitAndByte {
+                structgen_pad0: [sample.material_index1 & !BIT],
+            },
+        }
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum ModifySamplesError {
+    #[error("Row {row} has {len} samples, but the first row has {expected}")]
+    RaggedRows {
+        row: usize,
+        len: usize,
+        expected: usize,
+    },
+
+    #[error("There are no samples")]
+    Empty,
+
+    #[error("The height field rejected the samples")]
+    Failed,
+}
+
 #[repr(transparent)]
 pub struct HeightField {
     obj: physx_sys::PxHeightField,
@@ -24,6 +111,174 @@ impl HeightField {
     pub(crate) unsafe fn from_raw(ptr: *mut physx_sys::PxHeightField) -> Option<Owner<Self>> {
         unsafe { Owner::from_raw(ptr as *mut Self) }
     }
+
+    /// Get the number of sample rows.
+    pub fn get_nb_rows(&self) -> u32 {
+        unsafe { PxHeightField_getNbRows(self.as_ptr()) }
+    }
+
+    /// Get the number of sample columns.
+    pub fn get_nb_columns(&self) -> u32 {
+        unsafe { PxHeightField_getNbColumns(self.as_ptr()) }
+    }
+
+    /// Get the format of the samples.
+    pub fn get_format(&self) -> HeightFieldFormat {
+        unsafe { PxHeightField_getFormat(self.as_ptr()) }
+    }
+
+    /// Get the flags the height field was created with.
+    pub fn get_flags(&self) -> HeightFieldFlags {
+        unsafe { PxHeightField_getFlags(self.as_ptr()) }
+    }
+
+    /// Get the height difference below which edges are treated as convex.
+    pub fn get_convex_edge_threshold(&self) -> f32 {
+        unsafe { PxHeightField_getConvexEdgeThreshold(self.as_ptr()) }
+    }
+
+    /// Get a sample, or None if the row or column is out of range.
+    pub fn get_sample(&self, row: u32, column: u32) -> Option<HeightFieldSample> {
+        if row >= self.get_nb_rows() || column >= self.get_nb_columns() {
+            return None;
+        }
+        unsafe {
+            PxHeightField_getSample(self.as_ptr(), row, column)
+                .as_ref()
+                .map(|sample| (*sample).into())
+        }
+    }
+
+    /// Get all the samples, row by row.
+    pub fn save_cells(&self) -> Vec<HeightFieldSample> {
+        let len = (self.get_nb_rows() * self.get_nb_columns()) as usize;
+        let mut samples: Vec<physx_sys::PxHeightFieldSample> = Vec::with_capacity(len);
+        unsafe {
+            let written = PxHeightField_saveCells(
+                self.as_ptr(),
+                samples.as_mut_ptr().cast(),
+                (len * std::mem::size_of::<physx_sys::PxHeightFieldSample>()) as u32,
+            );
+            samples.set_len(
+                (written as usize / std::mem::size_of::<physx_sys::PxHeightFieldSample>()).min(len),
+            );
+        }
+        samples.into_iter().map(HeightFieldSample::from).collect()
+    }
+
+    /// Get the material index of a triangle.  Each cell has two triangles, triangle
+    /// `2 * (row * columns + column)` and the one after it.  Holes have the index
+    /// [`HeightFieldMaterial::Hole`].  Returns None if the index is out of bounds.
+    pub fn get_triangle_material_index(&self, triangle_index: u32) -> Option<u16> {
+        let nb_samples = u64::from(self.get_nb_rows()) * u64::from(self.get_nb_columns());
+        if u64::from(triangle_index) >= 2 * nb_samples {
+            return None;
+        }
+        Some(unsafe { PxHeightField_getTriangleMaterialIndex(self.as_ptr(), triangle_index) })
+    }
+
+    /// Get the interpolated height at a position in sample space, where `x` is the row
+    /// and `z` the column.  The position is clamped to the height field.
+    pub fn get_height(&self, x: f32, z: f32) -> f32 {
+        unsafe { PxHeightField_getHeight(self.as_ptr(), x, z) }
+    }
+
+    /// Get the normal of a triangle in sample space.  It is not normalized, and has to be
+    /// divided by the scales of a geometry before normalizing to get its normal.  Returns
+    /// None if the triangle is not in a cell of the height field.
+    pub fn get_triangle_normal(&self, triangle_index: u32) -> Option<PxVec3> {
+        if !self.has_cell_triangle(triangle_index) {
+            return None;
+        }
+        Some(unsafe { PxHeightField_getTriangleNormal(self.as_ptr(), triangle_index).into() })
+    }
+
+    /// Check that a triangle belongs to a cell, which PhysX reads along with the samples
+    /// of the next row and column.
+    pub(crate) fn has_cell_triangle(&self, triangle_index: u32) -> bool {
+        let columns = self.get_nb_columns();
+        let cell = triangle_index / 2;
+        columns != 0 && cell / columns + 1 < self.get_nb_rows() && cell % columns + 1 < columns
+    }
+
+    /// Get the index of the triangle at a position in sample space, where `x` is the row
+    /// and `z` the column, or None if the position is outside the height field.
+    pub fn get_triangle_index(&self, x: f32, z: f32) -> Option<u32> {
+        let (rows, columns) = (self.get_nb_rows(), self.get_nb_columns());
+        if rows < 2 || columns < 2 {
+            return None;
+        }
+        let (max_x, max_z) = ((rows - 1) as f32, (columns - 1) as f32);
+        if !(0.0..=max_x).contains(&x) || !(0.0..=max_z).contains(&z) {
+            return None;
+        }
+        let row = (x.floor() as u32).min(rows - 2);
+        let column = (z.floor() as u32).min(columns - 2);
+        let (frac_x, frac_z) = (x - row as f32, z - column as f32);
+        let second = if self.get_sample(row, column)?.tess_flag {
+            frac_z > frac_x
+        } else {
+            frac_x + frac_z > 1.0
+        };
+        Some(((row * columns + column) << 1) + second as u32)
+    }
+
+    /// Get the number of times the samples have been modified.
+    pub fn get_timestamp(&self) -> u32 {
+        unsafe { PxHeightField_getTimestamp(self.as_ptr()) }
+    }
+
+    /// Replace a region of samples, with the first sample at `start_row` and `start_column`.
+    /// `samples` holds the rows of the region, which must all have the same length.  Parts of
+    /// the region outside the height field are ignored.
+    ///
+    /// If `shrink_bounds` is false the bounds of the height field only grow, which is
+    /// cheaper when the heights change often.  Shapes using the height field do not see the
+    /// new samples until their geometry is set again.
+    pub fn modify_samples<R: AsRef<[HeightFieldSample]>>(
+        &mut self,
+        start_row: i32,
+        start_column: i32,
+        samples: &[R],
+        shrink_bounds: bool,
+    ) -> Result<(), ModifySamplesError> {
+        let nb_columns = samples.first().map_or(0, |row| row.as_ref().len());
+        if nb_columns == 0 {
+            return Err(ModifySamplesError::Empty);
+        }
+        let mut px_samples = Vec::with_capacity(samples.len() * nb_columns);
+        for (row, row_samples) in samples.iter().enumerate() {
+            let row_samples = row_samples.as_ref();
+            if row_samples.len() != nb_columns {
+                return Err(ModifySamplesError::RaggedRows {
+                    row,
+                    len: row_samples.len(),
+                    expected: nb_columns,
+                });
+            }
+            px_samples.extend(
+                row_samples
+                    .iter()
+                    .map(|&sample| physx_sys::PxHeightFieldSample::from(sample)),
+            );
+        }
+        let mut desc = PxHeightFieldDesc::new();
+        desc.set_samples(nb_columns as u32, &px_samples);
+        desc.obj.format = self.get_format();
+        if unsafe {
+            PxHeightField_modifySamples_mut(
+                self.as_mut_ptr(),
+                start_column,
+                start_row,
+                desc.as_ptr(),
+                shrink_bounds,
+            )
+        } {
+            Ok(())
+        } else {
+            Err(ModifySamplesError::Failed)
+        }
+    }
 }
 
 impl Drop for HeightField {
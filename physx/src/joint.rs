@@ -0,0 +1,493 @@
+//! Joints from the PhysX extensions library.
+//!
+//! Joints are owned by the user rather than the scene, and are added to the scene of the actors
+//! they connect.  The `new_to_world` constructors attach a joint to a fixed frame in the world
+//! instead of a second actor, except for gear and rack and pinion joints, which always couple
+//! the motion of two actors.
+
+mod contact_joint;
+mod d6_joint;
+mod distance_joint;
+mod fixed_joint;
+mod gear_joint;
+mod prismatic_joint;
+mod rack_and_pinion_joint;
+mod revolute_joint;
+mod spherical_joint;
+mod visualizer;
+
+use crate::{
+    math::{PxTransform, PxVec3},
+    traits::{Class, UserData},
+};
+
+use self::visualizer::VisualizerCallback;
+
+#[rustfmt::skip]
+use physx_sys::{
+    PxJointAngularLimitPair,
+    PxJointLimitCone,
+    PxJointLimitPyramid,
+    PxJointLinearLimit,
+    PxJointLinearLimitPair,
+    PxJoint_getBreakForce,
+    PxJoint_getConstraintFlags,
+    PxJoint_getInvInertiaScale0,
+    PxJoint_getInvInertiaScale1,
+    PxJoint_getInvMassScale0,
+    PxJoint_getInvMassScale1,
+    PxJoint_getLocalPose,
+    PxJoint_getRelativeAngularVelocity,
+    PxJoint_getRelativeLinearVelocity,
+    PxJoint_getRelativeTransform,
+    PxJoint_setBreakForce_mut,
+    PxJoint_setConstraintFlag_mut,
+    PxJoint_setConstraintFlags_mut,
+    PxJoint_setInvInertiaScale0_mut,
+    PxJoint_setInvInertiaScale1_mut,
+    PxJoint_setInvMassScale0_mut,
+    PxJoint_setInvMassScale1_mut,
+    PxJoint_setLocalPose_mut,
+    PxJoint_visualize,
+};
+
+#[rustfmt::skip]
+pub use physx_sys::{
+    PxConstraintFlag as ConstraintFlag,
+    PxConstraintFlags as ConstraintFlags,
+    PxJointActorIndex as JointActorIndex,
+};
+
+pub use self::contact_joint::{ContactJoint, JacobianRow, PxContactJoint};
+pub use self::d6_joint::{D6Axis, D6Drive, D6Joint, D6JointDrive, D6Motion, PxD6Joint};
+pub use self::distance_joint::{
+    DistanceJoint, DistanceJointFlag, DistanceJointFlags, PxDistanceJoint,
+};
+pub use self::fixed_joint::{FixedJoint, PxFixedJoint};
+pub use self::gear_joint::{GearJoint, HingeJoint, PxGearJoint};
+pub use self::prismatic_joint::{
+    PrismaticJoint, PrismaticJointFlag, PrismaticJointFlags, PxPrismaticJoint,
+};
+pub use self::rack_and_pinion_joint::{PxRackAndPinionJoint, RackAndPinionJoint, SliderJoint};
+pub use self::revolute_joint::{
+    PxRevoluteJoint, RevoluteJoint, RevoluteJointFlag, RevoluteJointFlags,
+};
+pub use self::spherical_joint::{
+    PxSphericalJoint, SphericalJoint, SphericalJointFlag, SphericalJointFlags,
+};
+pub use self::visualizer::{ConstraintVisualizationFlags, ConstraintVisualizer};
+
+/// Functionality shared by all joints.
+pub trait Joint: Class<physx_sys::PxJoint> + UserData {
+    /// Get the user data.
+    fn get_user_data(&self) -> &Self::UserData {
+        // Safety: all construction goes through from_raw, which calls init_user_data
+        unsafe { UserData::get_user_data(self) }
+    }
+
+    /// Get the user data.
+    fn get_user_data_mut(&mut self) -> &mut Self::UserData {
+        // Safety: all construction goes through from_raw, which calls init_user_data
+        unsafe { UserData::get_user_data_mut(self) }
+    }
+
+    /// Set the joint frame of one of the actors, relative to that actor.
+    fn set_local_pose(&mut self, actor: JointActorIndex, local_pose: &PxTransform) {
+        unsafe { PxJoint_setLocalPose_mut(self.as_mut_ptr(), actor, local_pose.as_ptr()) }
+    }
+
+    /// Get the joint frame of one of the actors, relative to that actor.
+    fn get_local_pose(&self, actor: JointActorIndex) -> PxTransform {
+        unsafe { PxJoint_getLocalPose(self.as_ptr(), actor) }.into()
+    }
+
+    /// Get the pose of the second actor's joint frame relative to the first's.
+    fn get_relative_transform(&self) -> PxTransform {
+        unsafe { PxJoint_getRelativeTransform(self.as_ptr()) }.into()
+    }
+
+    /// Get the linear velocity of the second actor's joint frame relative to the first's.
+    fn get_relative_linear_velocity(&self) -> PxVec3 {
+        unsafe { PxJoint_getRelativeLinearVelocity(self.as_ptr()) }.into()
+    }
+
+    /// Get the angular velocity of the second actor's joint frame relative to the first's.
+    fn get_relative_angular_velocity(&self) -> PxVec3 {
+        unsafe { PxJoint_getRelativeAngularVelocity(self.as_ptr()) }.into()
+    }
+
+    /// Set the force and torque at which the joint breaks.  The default is `f32::MAX` for both.
+    fn set_break_force(&mut self, force: f32, torque: f32) {
+        unsafe { PxJoint_setBreakForce_mut(self.as_mut_ptr(), force, torque) }
+    }
+
+    /// Get the force and torque at which the joint breaks.
+    fn get_break_force(&self) -> (f32, f32) {
+        let mut force = 0.0;
+        let mut torque = 0.0;
+        unsafe { PxJoint_getBreakForce(self.as_ptr(), &mut force, &mut torque) };
+        (force, torque)
+    }
+
+    /// Returns true if the joint has been broken by exceeding its break force.
+    fn is_broken(&self) -> bool {
+        self.get_constraint_flags()
+            .contains(ConstraintFlags::Broken)
+    }
+
+    /// Set all the constraint flags of the joint.
+    fn set_constraint_flags(&mut self, flags: ConstraintFlags) {
+        unsafe { PxJoint_setConstraintFlags_mut(self.as_mut_ptr(), flags) }
+    }
+
+    /// Set a single constraint flag of the joint.
+    fn set_constraint_flag(&mut self, flag: ConstraintFlag, value: bool) {
+        unsafe { PxJoint_setConstraintFlag_mut(self.as_mut_ptr(), flag, value) }
+    }
+
+    /// Get the constraint flags of the joint.
+    fn get_constraint_flags(&self) -> ConstraintFlags {
+        unsafe { PxJoint_getConstraintFlags(self.as_ptr()) }
+    }
+
+    /// Set the inverse mass scale of the first actor.
+    fn set_inv_mass_scale0(&mut self, inv_mass_scale: f32) {
+        unsafe { PxJoint_setInvMassScale0_mut(self.as_mut_ptr(), inv_mass_scale) }
+    }
+
+    /// Get the inverse mass scale of the first actor.
+    fn get_inv_mass_scale0(&self) -> f32 {
+        unsafe { PxJoint_getInvMassScale0(self.as_ptr()) }
+    }
+
+    /// Set the inverse inertia scale of the first actor.
+    fn set_inv_inertia_scale0(&mut self, inv_inertia_scale: f32) {
+        unsafe { PxJoint_setInvInertiaScale0_mut(self.as_mut_ptr(), inv_inertia_scale) }
+    }
+
+    /// Get the inverse inertia scale of the first actor.
+    fn get_inv_inertia_scale0(&self) -> f32 {
+        unsafe { PxJoint_getInvInertiaScale0(self.as_ptr()) }
+    }
+
+    /// Set the inverse mass scale of the second actor.
+    fn set_inv_mass_scale1(&mut self, inv_mass_scale: f32) {
+        unsafe { PxJoint_setInvMassScale1_mut(self.as_mut_ptr(), inv_mass_scale) }
+    }
+
+    /// Get the inverse mass scale of the second actor.
+    fn get_inv_mass_scale1(&self) -> f32 {
+        unsafe { PxJoint_getInvMassScale1(self.as_ptr()) }
+    }
+
+    /// Set the inverse inertia scale of the second actor.
+    fn set_inv_inertia_scale1(&mut self, inv_inertia_scale: f32) {
+        unsafe { PxJoint_setInvInertiaScale1_mut(self.as_mut_ptr(), inv_inertia_scale) }
+    }
+
+    /// Get the inverse inertia scale of the second actor.
+    fn get_inv_inertia_scale1(&self) -> f32 {
+        unsafe { PxJoint_getInvInertiaScale1(self.as_ptr()) }
+    }
+
+    /// Emit the joint frames and limits selected by `flags` into `visualizer`, using the same
+    /// shapes PhysX uses for its own debug visualization.  Returns false if the joint has nothing
+    /// to visualize.
+    fn visualize(
+        &self,
+        visualizer: &mut impl ConstraintVisualizer,
+        flags: ConstraintVisualizationFlags,
+    ) -> bool {
+        let mut callback = VisualizerCallback::new(visualizer);
+        unsafe { PxJoint_visualize(self.as_ptr(), callback.as_mut_ptr(), flags.bits()) }
+    }
+}
+
+/// Parameters shared by all joint limits.  A limit with a stiffness above zero is soft,
+/// and acts as a spring pulling the joint back within the limit.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct LimitParameters {
+    /// How much of the velocity is reversed when a hard limit is hit.
+    pub restitution: f32,
+    /// Relative velocity below which restitution is ignored.
+    pub bounce_threshold: f32,
+    pub stiffness: f32,
+    pub damping: f32,
+}
+
+/// A limit on the distance from a point, used by the D6 joint.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct LinearLimit {
+    pub value: f32,
+    pub params: LimitParameters,
+}
+
+/// A lower and upper limit on a linear position.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct LinearLimitPair {
+    pub lower: f32,
+    pub upper: f32,
+    pub params: LimitParameters,
+}
+
+/// A lower and upper limit on an angle, in radians.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct AngularLimitPair {
+    pub lower: f32,
+    pub upper: f32,
+    pub params: LimitParameters,
+}
+
+/// An elliptical cone limit on the swing of a joint, the angles are in radians.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct LimitCone {
+    pub y_angle: f32,
+    pub z_angle: f32,
+    pub params: LimitParameters,
+}
+
+/// A pyramidal limit on the swing of a joint, the angles are in radians.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct LimitPyramid {
+    pub y_angle_min: f32,
+    pub y_angle_max: f32,
+    pub z_angle_min: f32,
+    pub z_angle_max: f32,
+    pub params: LimitParameters,
+}
+
+impl LinearLimit {
+    pub fn new(value: f32) -> Self {
+        Self {
+            value,
+            ..Default::default()
+        }
+    }
+}
+
+impl LinearLimitPair {
+    pub fn new(lower: f32, upper: f32) -> Self {
+        Self {
+            lower,
+            upper,
+            ..Default::default()
+        }
+    }
+}
+
+impl AngularLimitPair {
+    pub fn new(lower: f32, upper: f32) -> Self {
+        Self {
+            lower,
+            upper,
+            ..Default::default()
+        }
+    }
+}
+
+impl LimitCone {
+    pub fn new(y_angle: f32, z_angle: f32) -> Self {
+        Self {
+            y_angle,
+            z_angle,
+            ..Default::default()
+        }
+    }
+}
+
+impl LimitPyramid {
+    pub fn new(y_angle_min: f32, y_angle_max: f32, z_angle_min: f32, z_angle_max: f32) -> Self {
+        Self {
+            y_angle_min,
+            y_angle_max,
+            z_angle_min,
+            z_angle_max,
+            ..Default::default()
+        }
+    }
+}
+
+// The limit types all start with the same fields, the contact distance is deprecated and left at zero.
+macro_rules! ConvertLimit {
+    ($Limit:ident <=> $PxLimit:ident { $($field:ident <=> $px_field:ident),* }) => {
+        impl From<$Limit> for $PxLimit {
+            fn from(value: $Limit) -> Self {
+                Self {
+                    restitution: value.params.restitution,
+                    bounceThreshold: value.params.bounce_threshold,
+                    stiffness: value.params.stiffness,
+                    damping: value.params.damping,
+                    contactDistance_deprecated: 0.0,
+                    $($px_field: value.$field,)*
+                }
+            }
+        }
+
+        impl From<$PxLimit> for $Limit {
+            fn from(value: $PxLimit) -> Self {
+                Self {
+                    params: LimitParameters {
+                        restitution: value.restitution,
+                        bounce_threshold: value.bounceThreshold,
+                        stiffness: value.stiffness,
+                        damping: value.damping,
+                    },
+                    $($field: value.$px_field,)*
+                }
+            }
+        }
+    };
+}
+
+ConvertLimit!(LinearLimit <=> PxJointLinearLimit { value <=> value });
+ConvertLimit!(LinearLimitPair <=> PxJointLinearLimitPair { lower <=> lower, upper <=> upper });
+ConvertLimit!(AngularLimitPair <=> PxJointAngularLimitPair { lower <=> lower, upper <=> upper });
+ConvertLimit!(LimitCone <=> PxJointLimitCone { y_angle <=> yAngle, z_angle <=> zAngle });
+ConvertLimit!(LimitPyramid <=> PxJointLimitPyramid {
+    y_angle_min <=> yAngleMin,
+    y_angle_max <=> yAngleMax,
+    z_angle_min <=> zAngleMin,
+    z_angle_max <=> zAngleMax
+});
+
+/// Declare the new type wrapper of a joint, parametrized by its user data type, with the
+/// impls shared by all joints.
+macro_rules! DeclareJoint {
+    ($(#[$meta:meta])* $PxJoint:ident: $JointTrait:ident) => {
+        $(#[$meta])*
+        #[repr(transparent)]
+        pub struct $PxJoint<U> {
+            obj: ::physx_sys::$PxJoint,
+            phantom_user_data: ::std::marker::PhantomData<U>,
+        }
+
+        unsafe impl<U> $crate::traits::UserData for $PxJoint<U> {
+            type UserData = U;
+
+            fn user_data_ptr(&self) -> &*mut ::std::ffi::c_void {
+                &self.obj.userData
+            }
+
+            fn user_data_ptr_mut(&mut self) -> &mut *mut ::std::ffi::c_void {
+                &mut self.obj.userData
+            }
+        }
+
+        impl<U> Drop for $PxJoint<U> {
+            fn drop(&mut self) {
+                unsafe {
+                    ::std::ptr::drop_in_place(
+                        $crate::joint::Joint::get_user_data_mut(self) as *mut _
+                    );
+                    ::physx_sys::PxJoint_release_mut($crate::traits::Class::as_mut_ptr(self))
+                }
+            }
+        }
+
+        unsafe impl<P, U> $crate::traits::Class<P> for $PxJoint<U>
+        where
+            ::physx_sys::$PxJoint: $crate::traits::Class<P>,
+        {
+            fn as_ptr(&self) -> *const P {
+                $crate::traits::Class::as_ptr(&self.obj)
+            }
+
+            fn as_mut_ptr(&mut self) -> *mut P {
+                $crate::traits::Class::as_mut_ptr(&mut self.obj)
+            }
+        }
+
+        unsafe impl<U: Send> Send for $PxJoint<U> {}
+        unsafe impl<U: Sync> Sync for $PxJoint<U> {}
+
+        impl<U> $crate::joint::Joint for $PxJoint<U> {}
+        impl<U> $JointTrait for $PxJoint<U> {}
+    };
+}
+
+/// Declare `new`, `from_raw` and `get_concrete_type_name` in the trait of a joint.
+macro_rules! JointConstructors {
+    ($name:literal, $PxJoint:ident, $create:ident, $get_concrete_type_name:ident) => {
+        #[doc = concat!(
+                    "Create a ", $name, " joint between two actors.  ",
+                    "The local frames are relative to each actor."
+                )]
+        fn new(
+            physics: &mut impl $crate::physics::Physics,
+            actor0: &mut impl $crate::rigid_actor::RigidActor,
+            local_frame0: &$crate::math::PxTransform,
+            actor1: &mut impl $crate::rigid_actor::RigidActor,
+            local_frame1: &$crate::math::PxTransform,
+            user_data: Self::UserData,
+        ) -> Option<$crate::owner::Owner<Self>> {
+            unsafe {
+                Self::from_raw(
+                    ::physx_sys::$create(
+                        $crate::traits::Class::as_mut_ptr(physics),
+                        $crate::traits::Class::as_mut_ptr(actor0),
+                        $crate::traits::Class::as_ptr(local_frame0),
+                        $crate::traits::Class::as_mut_ptr(actor1),
+                        $crate::traits::Class::as_ptr(local_frame1),
+                    ),
+                    user_data,
+                )
+            }
+        }
+
+        /// # Safety
+        /// Owner's own the pointer they wrap, using the pointer after dropping the Owner,
+        /// or creating multiple Owners from the same pointer will cause UB.  Use `into_ptr` to
+        /// retrieve the pointer and consume the Owner without dropping the pointee.
+        /// Initializes user data.
+        unsafe fn from_raw(
+            ptr: *mut ::physx_sys::$PxJoint,
+            user_data: Self::UserData,
+        ) -> Option<$crate::owner::Owner<Self>> {
+            unsafe {
+                let joint = (ptr as *mut Self).as_mut();
+                let joint = $crate::traits::UserData::init_user_data(joint?, user_data);
+                $crate::owner::Owner::from_raw(joint)
+            }
+        }
+
+        /// Get the name of the real type referenced by this pointer, or None if the returned string is not valid
+        fn get_concrete_type_name(&self) -> Option<&str> {
+            unsafe {
+                let name =
+                    ::physx_sys::$get_concrete_type_name($crate::traits::Class::as_ptr(self));
+                ::std::ffi::CStr::from_ptr(name as _).to_str().ok()
+            }
+        }
+    };
+}
+
+/// Declare `new_to_world` in the trait of a joint that can attach an actor to the world.
+macro_rules! JointToWorldConstructor {
+    ($name:literal, $create:ident) => {
+        #[doc = concat!("Create a ", $name, " joint between a frame in the world and an actor.")]
+        fn new_to_world(
+            physics: &mut impl $crate::physics::Physics,
+            world_frame: &$crate::math::PxTransform,
+            actor: &mut impl $crate::rigid_actor::RigidActor,
+            local_frame: &$crate::math::PxTransform,
+            user_data: Self::UserData,
+        ) -> Option<$crate::owner::Owner<Self>> {
+            unsafe {
+                Self::from_raw(
+                    ::physx_sys::$create(
+                        $crate::traits::Class::as_mut_ptr(physics),
+                        ::std::ptr::null_mut(),
+                        $crate::traits::Class::as_ptr(world_frame),
+                        $crate::traits::Class::as_mut_ptr(actor),
+                        $crate::traits::Class::as_ptr(local_frame),
+                    ),
+                    user_data,
+                )
+            }
+        }
+    };
+}
+
+pub(crate) use DeclareJoint;
+pub(crate) use JointConstructors;
+pub(crate) use JointToWorldConstructor;
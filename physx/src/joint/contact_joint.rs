@@ -0,0 +1,95 @@
+use crate::{joint::Joint, math::PxVec3, traits::Class};
+
+#[rustfmt::skip]
+use physx_sys::{
+    PxContactJoint_computeJacobians,
+    PxContactJoint_getBounceThreshold,
+    PxContactJoint_getContact,
+    PxContactJoint_getContactNormal,
+    PxContactJoint_getNbJacobianRows,
+    PxContactJoint_getPenetration,
+    PxContactJoint_getRestitution,
+    PxContactJoint_setBounceThreshold_mut,
+    PxContactJoint_setContactNormal_mut,
+    PxContactJoint_setContact_mut,
+    PxContactJoint_setPenetration_mut,
+    PxContactJoint_setRestitution_mut,
+    PxJacobianRow_new,
+};
+
+pub use physx_sys::PxJacobianRow as JacobianRow;
+
+crate::joint::DeclareJoint!(
+    /// A new type wrapper for PxContactJoint.  Parametrized by its user data type.
+    PxContactJoint: ContactJoint
+);
+
+/// A joint that acts like a single contact point between two actors, pushing them apart along the
+/// contact normal while they penetrate.
+pub trait ContactJoint: Class<physx_sys::PxContactJoint> + Joint {
+    crate::joint::JointConstructors!(
+        "contact",
+        PxContactJoint,
+        phys_PxContactJointCreate,
+        PxContactJoint_getConcreteTypeName
+    );
+    crate::joint::JointToWorldConstructor!("contact", phys_PxContactJointCreate);
+
+    /// Set the contact point, in world space.
+    fn set_contact(&mut self, contact: &PxVec3) {
+        unsafe { PxContactJoint_setContact_mut(self.as_mut_ptr(), contact.as_ptr()) }
+    }
+
+    /// Get the contact point, in world space.
+    fn get_contact(&self) -> PxVec3 {
+        unsafe { PxContactJoint_getContact(self.as_ptr()) }.into()
+    }
+
+    /// Set the contact normal, pointing from the second actor towards the first.
+    fn set_contact_normal(&mut self, contact_normal: &PxVec3) {
+        unsafe { PxContactJoint_setContactNormal_mut(self.as_mut_ptr(), contact_normal.as_ptr()) }
+    }
+
+    /// Get the contact normal.
+    fn get_contact_normal(&self) -> PxVec3 {
+        unsafe { PxContactJoint_getContactNormal(self.as_ptr()) }.into()
+    }
+
+    /// Set the penetration depth, negative values mean the actors are penetrating.
+    fn set_penetration(&mut self, penetration: f32) {
+        unsafe { PxContactJoint_setPenetration_mut(self.as_mut_ptr(), penetration) }
+    }
+
+    /// Get the penetration depth.
+    fn get_penetration(&self) -> f32 {
+        unsafe { PxContactJoint_getPenetration(self.as_ptr()) }
+    }
+
+    /// Set the restitution of the contact, in the range [0, 1].
+    fn set_restitution(&mut self, restitution: f32) {
+        unsafe { PxContactJoint_setRestitution_mut(self.as_mut_ptr(), restitution) }
+    }
+
+    /// Get the restitution of the contact.
+    fn get_restitution(&self) -> f32 {
+        unsafe { PxContactJoint_getRestitution(self.as_ptr()) }
+    }
+
+    /// Set the relative velocity below which restitution is ignored.
+    fn set_bounce_threshold(&mut self, bounce_threshold: f32) {
+        unsafe { PxContactJoint_setBounceThreshold_mut(self.as_mut_ptr(), bounce_threshold) }
+    }
+
+    /// Get the relative velocity below which restitution is ignored.
+    fn get_bounce_threshold(&self) -> f32 {
+        unsafe { PxContactJoint_getBounceThreshold(self.as_ptr()) }
+    }
+
+    /// Compute the jacobian rows of the constraint for the current poses of the actors.
+    fn compute_jacobians(&self) -> Vec<JacobianRow> {
+        let nb_rows = unsafe { PxContactJoint_getNbJacobianRows(self.as_ptr()) } as usize;
+        let mut rows = vec![unsafe { PxJacobianRow_new() }; nb_rows];
+        unsafe { PxContactJoint_computeJacobians(self.as_ptr(), rows.as_mut_ptr()) };
+        rows
+    }
+}
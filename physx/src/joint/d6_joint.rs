@@ -0,0 +1,226 @@
+use crate::{
+    joint::{AngularLimitPair, Joint, LimitCone, LimitPyramid, LinearLimit, LinearLimitPair},
+    math::{PxTransform, PxVec3},
+    traits::Class,
+};
+
+#[rustfmt::skip]
+use physx_sys::{
+    PxD6JointDrive,
+    PxD6JointDriveFlags,
+    PxD6Joint_getDistanceLimit,
+    PxD6Joint_getDrive,
+    PxD6Joint_getDrivePosition,
+    PxD6Joint_getDriveVelocity,
+    PxD6Joint_getLinearLimit,
+    PxD6Joint_getMotion,
+    PxD6Joint_getPyramidSwingLimit,
+    PxD6Joint_getSwingLimit,
+    PxD6Joint_getSwingYAngle,
+    PxD6Joint_getSwingZAngle,
+    PxD6Joint_getTwistAngle,
+    PxD6Joint_getTwistLimit,
+    PxD6Joint_setDistanceLimit_mut,
+    PxD6Joint_setDrivePosition_mut,
+    PxD6Joint_setDriveVelocity_mut,
+    PxD6Joint_setDrive_mut,
+    PxD6Joint_setLinearLimit_mut,
+    PxD6Joint_setMotion_mut,
+    PxD6Joint_setPyramidSwingLimit_mut,
+    PxD6Joint_setSwingLimit_mut,
+    PxD6Joint_setTwistLimit_mut,
+};
+
+#[rustfmt::skip]
+pub use physx_sys::{
+    PxD6Axis as D6Axis,
+    PxD6Drive as D6Drive,
+    PxD6Motion as D6Motion,
+};
+
+/// The parameters of one of the drives of a D6 joint.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct D6JointDrive {
+    pub stiffness: f32,
+    pub damping: f32,
+    /// The maximum force or torque the drive can exert.
+    pub force_limit: f32,
+    /// If set, the drive acts on acceleration instead of force, which makes it independent of mass.
+    pub is_acceleration: bool,
+}
+
+impl D6JointDrive {
+    pub fn new(stiffness: f32, damping: f32, force_limit: f32, is_acceleration: bool) -> Self {
+        Self {
+            stiffness,
+            damping,
+            force_limit,
+            is_acceleration,
+        }
+    }
+}
+
+impl Default for D6JointDrive {
+    fn default() -> Self {
+        Self::new(0.0, 0.0, f32::MAX, false)
+    }
+}
+
+impl From<D6JointDrive> for PxD6JointDrive {
+    fn from(value: D6JointDrive) -> Self {
+        Self {
+            stiffness: value.stiffness,
+            damping: value.damping,
+            forceLimit: value.force_limit,
+            flags: if value.is_acceleration {
+                PxD6JointDriveFlags::Acceleration
+            } else {
+                PxD6JointDriveFlags::empty()
+            },
+        }
+    }
+}
+
+impl From<PxD6JointDrive> for D6JointDrive {
+    fn from(value: PxD6JointDrive) -> Self {
+        Self {
+            stiffness: value.stiffness,
+            damping: value.damping,
+            force_limit: value.forceLimit,
+            is_acceleration: value.flags.contains(PxD6JointDriveFlags::Acceleration),
+        }
+    }
+}
+
+crate::joint::DeclareJoint!(
+    /// A new type wrapper for PxD6Joint.  Parametrized by its user data type.
+    PxD6Joint: D6Joint
+);
+
+/// A configurable joint, each of the six degrees of freedom can be locked, limited or free.
+pub trait D6Joint: Class<physx_sys::PxD6Joint> + Joint {
+    crate::joint::JointConstructors!(
+        "D6",
+        PxD6Joint,
+        phys_PxD6JointCreate,
+        PxD6Joint_getConcreteTypeName
+    );
+    crate::joint::JointToWorldConstructor!("D6", phys_PxD6JointCreate);
+
+    /// Set the motion type of a degree of freedom.  All are locked by default.
+    fn set_motion(&mut self, axis: D6Axis, motion: D6Motion) {
+        unsafe { PxD6Joint_setMotion_mut(self.as_mut_ptr(), axis, motion) }
+    }
+
+    /// Get the motion type of a degree of freedom.
+    fn get_motion(&self, axis: D6Axis) -> D6Motion {
+        unsafe { PxD6Joint_getMotion(self.as_ptr(), axis) }
+    }
+
+    /// Get the twist angle of the joint, in the range (-2*Pi, 2*Pi].
+    fn get_twist_angle(&self) -> f32 {
+        unsafe { PxD6Joint_getTwistAngle(self.as_ptr()) }
+    }
+
+    /// Get the swing angle of the joint around the y axis.
+    fn get_swing_y_angle(&self) -> f32 {
+        unsafe { PxD6Joint_getSwingYAngle(self.as_ptr()) }
+    }
+
+    /// Get the swing angle of the joint around the z axis.
+    fn get_swing_z_angle(&self) -> f32 {
+        unsafe { PxD6Joint_getSwingZAngle(self.as_ptr()) }
+    }
+
+    /// Set the distance limit, used when any linear axis is limited.
+    fn set_distance_limit(&mut self, limit: LinearLimit) {
+        unsafe { PxD6Joint_setDistanceLimit_mut(self.as_mut_ptr(), &limit.into()) }
+    }
+
+    /// Get the distance limit.
+    fn get_distance_limit(&self) -> LinearLimit {
+        unsafe { PxD6Joint_getDistanceLimit(self.as_ptr()) }.into()
+    }
+
+    /// Set the limit of a linear axis.
+    fn set_linear_limit(&mut self, axis: D6Axis, limit: LinearLimitPair) {
+        unsafe { PxD6Joint_setLinearLimit_mut(self.as_mut_ptr(), axis, &limit.into()) }
+    }
+
+    /// Get the limit of a linear axis.
+    fn get_linear_limit(&self, axis: D6Axis) -> LinearLimitPair {
+        unsafe { PxD6Joint_getLinearLimit(self.as_ptr(), axis) }.into()
+    }
+
+    /// Set the twist limit, used when the twist axis is limited.
+    fn set_twist_limit(&mut self, limit: AngularLimitPair) {
+        unsafe { PxD6Joint_setTwistLimit_mut(self.as_mut_ptr(), &limit.into()) }
+    }
+
+    /// Get the twist limit.
+    fn get_twist_limit(&self) -> AngularLimitPair {
+        unsafe { PxD6Joint_getTwistLimit(self.as_ptr()) }.into()
+    }
+
+    /// Set the cone swing limit, used when either swing axis is limited.
+    fn set_swing_limit(&mut self, limit: LimitCone) {
+        unsafe { PxD6Joint_setSwingLimit_mut(self.as_mut_ptr(), &limit.into()) }
+    }
+
+    /// Get the cone swing limit.
+    fn get_swing_limit(&self) -> LimitCone {
+        unsafe { PxD6Joint_getSwingLimit(self.as_ptr()) }.into()
+    }
+
+    /// Set the pyramid swing limit, used instead of the cone limit when both swing axes are limited.
+    fn set_pyramid_swing_limit(&mut self, limit: LimitPyramid) {
+        unsafe { PxD6Joint_setPyramidSwingLimit_mut(self.as_mut_ptr(), &limit.into()) }
+    }
+
+    /// Get the pyramid swing limit.
+    fn get_pyramid_swing_limit(&self) -> LimitPyramid {
+        unsafe { PxD6Joint_getPyramidSwingLimit(self.as_ptr()) }.into()
+    }
+
+    /// Set the parameters of a drive.
+    fn set_drive(&mut self, index: D6Drive, drive: D6JointDrive) {
+        unsafe { PxD6Joint_setDrive_mut(self.as_mut_ptr(), index, &drive.into()) }
+    }
+
+    /// Get the parameters of a drive.
+    fn get_drive(&self, index: D6Drive) -> D6JointDrive {
+        unsafe { PxD6Joint_getDrive(self.as_ptr(), index) }.into()
+    }
+
+    /// Set the target pose of the drives, relative to the first actor's joint frame.
+    fn set_drive_position(&mut self, pose: &PxTransform, autowake: bool) {
+        unsafe { PxD6Joint_setDrivePosition_mut(self.as_mut_ptr(), pose.as_ptr(), autowake) }
+    }
+
+    /// Get the target pose of the drives.
+    fn get_drive_position(&self) -> PxTransform {
+        unsafe { PxD6Joint_getDrivePosition(self.as_ptr()) }.into()
+    }
+
+    /// Set the target linear and angular velocity of the drives.
+    fn set_drive_velocity(&mut self, linear: &PxVec3, angular: &PxVec3, autowake: bool) {
+        unsafe {
+            PxD6Joint_setDriveVelocity_mut(
+                self.as_mut_ptr(),
+                linear.as_ptr(),
+                angular.as_ptr(),
+                autowake,
+            )
+        }
+    }
+
+    /// Get the target linear and angular velocity of the drives.
+    fn get_drive_velocity(&self) -> (PxVec3, PxVec3) {
+        let mut linear = PxVec3::default();
+        let mut angular = PxVec3::default();
+        unsafe {
+            PxD6Joint_getDriveVelocity(self.as_ptr(), linear.as_mut_ptr(), angular.as_mut_ptr())
+        };
+        (linear, angular)
+    }
+}
@@ -0,0 +1,123 @@
+use crate::{joint::Joint, traits::Class};
+
+#[rustfmt::skip]
+use physx_sys::{
+    PxDistanceJoint_getContactDistance,
+    PxDistanceJoint_getDamping,
+    PxDistanceJoint_getDistance,
+    PxDistanceJoint_getDistanceJointFlags,
+    PxDistanceJoint_getMaxDistance,
+    PxDistanceJoint_getMinDistance,
+    PxDistanceJoint_getStiffness,
+    PxDistanceJoint_getTolerance,
+    PxDistanceJoint_setContactDistance_mut,
+    PxDistanceJoint_setDamping_mut,
+    PxDistanceJoint_setDistanceJointFlag_mut,
+    PxDistanceJoint_setDistanceJointFlags_mut,
+    PxDistanceJoint_setMaxDistance_mut,
+    PxDistanceJoint_setMinDistance_mut,
+    PxDistanceJoint_setStiffness_mut,
+    PxDistanceJoint_setTolerance_mut,
+};
+
+#[rustfmt::skip]
+pub use physx_sys::{
+    PxDistanceJointFlag as DistanceJointFlag,
+    PxDistanceJointFlags as DistanceJointFlags,
+};
+
+crate::joint::DeclareJoint!(
+    /// A new type wrapper for PxDistanceJoint.  Parametrized by its user data type.
+    PxDistanceJoint: DistanceJoint
+);
+
+/// A joint that keeps the distance between two points on the actors within a range.
+pub trait DistanceJoint: Class<physx_sys::PxDistanceJoint> + Joint {
+    crate::joint::JointConstructors!(
+        "distance",
+        PxDistanceJoint,
+        phys_PxDistanceJointCreate,
+        PxDistanceJoint_getConcreteTypeName
+    );
+    crate::joint::JointToWorldConstructor!("distance", phys_PxDistanceJointCreate);
+
+    /// Get the current distance between the joint frames.
+    fn get_distance(&self) -> f32 {
+        unsafe { PxDistanceJoint_getDistance(self.as_ptr()) }
+    }
+
+    /// Set the minimum distance.  Only used if `DistanceJointFlag::MinDistanceEnabled` is set.
+    fn set_min_distance(&mut self, distance: f32) {
+        unsafe { PxDistanceJoint_setMinDistance_mut(self.as_mut_ptr(), distance) }
+    }
+
+    /// Get the minimum distance.
+    fn get_min_distance(&self) -> f32 {
+        unsafe { PxDistanceJoint_getMinDistance(self.as_ptr()) }
+    }
+
+    /// Set the maximum distance.  Only used if `DistanceJointFlag::MaxDistanceEnabled` is set.
+    fn set_max_distance(&mut self, distance: f32) {
+        unsafe { PxDistanceJoint_setMaxDistance_mut(self.as_mut_ptr(), distance) }
+    }
+
+    /// Get the maximum distance.
+    fn get_max_distance(&self) -> f32 {
+        unsafe { PxDistanceJoint_getMaxDistance(self.as_ptr()) }
+    }
+
+    /// Set the distance beyond the allowed range at which the joint becomes active.
+    fn set_tolerance(&mut self, tolerance: f32) {
+        unsafe { PxDistanceJoint_setTolerance_mut(self.as_mut_ptr(), tolerance) }
+    }
+
+    /// Get the distance beyond the allowed range at which the joint becomes active.
+    fn get_tolerance(&self) -> f32 {
+        unsafe { PxDistanceJoint_getTolerance(self.as_ptr()) }
+    }
+
+    /// Set the stiffness of the spring.  Only used if `DistanceJointFlag::SpringEnabled` is set.
+    fn set_stiffness(&mut self, stiffness: f32) {
+        unsafe { PxDistanceJoint_setStiffness_mut(self.as_mut_ptr(), stiffness) }
+    }
+
+    /// Get the stiffness of the spring.
+    fn get_stiffness(&self) -> f32 {
+        unsafe { PxDistanceJoint_getStiffness(self.as_ptr()) }
+    }
+
+    /// Set the damping of the spring.  Only used if `DistanceJointFlag::SpringEnabled` is set.
+    fn set_damping(&mut self, damping: f32) {
+        unsafe { PxDistanceJoint_setDamping_mut(self.as_mut_ptr(), damping) }
+    }
+
+    /// Get the damping of the spring.
+    fn get_damping(&self) -> f32 {
+        unsafe { PxDistanceJoint_getDamping(self.as_ptr()) }
+    }
+
+    /// Set the contact distance of the min and max distance limits.
+    fn set_contact_distance(&mut self, contact_distance: f32) {
+        unsafe { PxDistanceJoint_setContactDistance_mut(self.as_mut_ptr(), contact_distance) }
+    }
+
+    /// Get the contact distance of the min and max distance limits.
+    fn get_contact_distance(&self) -> f32 {
+        unsafe { PxDistanceJoint_getContactDistance(self.as_ptr()) }
+    }
+
+    /// Set all the flags of the joint.
+    fn set_distance_joint_flags(&mut self, flags: DistanceJointFlags) {
+        unsafe { PxDistanceJoint_setDistanceJointFlags_mut(self.as_mut_ptr(), flags) }
+    }
+
+    /// Set a single flag of the joint.
+    fn set_distance_joint_flag(&mut self, flag: DistanceJointFlag, value: bool) {
+        unsafe { PxDistanceJoint_setDistanceJointFlag_mut(self.as_mut_ptr(), flag, value) }
+    }
+
+    /// Get the flags of the joint.
+    fn get_distance_joint_flags(&self) -> DistanceJointFlags {
+        unsafe { PxDistanceJoint_getDistanceJointFlags(self.as_ptr()) }
+    }
+}
@@ -0,0 +1,17 @@
+use crate::{joint::Joint, traits::Class};
+
+crate::joint::DeclareJoint!(
+    /// A new type wrapper for PxFixedJoint.  Parametrized by its user data type.
+    PxFixedJoint: FixedJoint
+);
+
+/// A joint that removes all relative motion between two actors.
+pub trait FixedJoint: Class<physx_sys::PxFixedJoint> + Joint {
+    crate::joint::JointConstructors!(
+        "fixed",
+        PxFixedJoint,
+        phys_PxFixedJointCreate,
+        PxFixedJoint_getConcreteTypeName
+    );
+    crate::joint::JointToWorldConstructor!("fixed", phys_PxFixedJointCreate);
+}
@@ -0,0 +1,63 @@
+use crate::{
+    joint::{Joint, PxD6Joint, PxRevoluteJoint},
+    traits::Class,
+};
+
+#[rustfmt::skip]
+use physx_sys::{
+    PxBase,
+    PxGearJoint_getGearRatio,
+    PxGearJoint_setGearRatio_mut,
+    PxGearJoint_setHinges_mut,
+    PxJoint,
+};
+
+crate::joint::DeclareJoint!(
+    /// A new type wrapper for PxGearJoint.  Parametrized by its user data type.
+    PxGearJoint: GearJoint
+);
+
+/// Joints rotating around a single axis, which can be the hinges of a gear joint or the pinion
+/// of a rack and pinion joint.  Implemented by revolute joints, and D6 joints with a single
+/// free angular axis.
+pub trait HingeJoint: Joint {}
+
+impl<U> HingeJoint for PxRevoluteJoint<U> {}
+impl<U> HingeJoint for PxD6Joint<U> {}
+
+/// A joint that couples the rotation of two hinge joints with a gear ratio.
+///
+/// The actors of the gear joint must be the actors the two hinges rotate.
+pub trait GearJoint: Class<physx_sys::PxGearJoint> + Joint {
+    crate::joint::JointConstructors!(
+        "gear",
+        PxGearJoint,
+        phys_PxGearJointCreate,
+        PxGearJoint_getConcreteTypeName
+    );
+
+    /// Set the two hinge joints whose rotations are coupled.  Returns false if PhysX rejects them.
+    ///
+    /// # Safety
+    /// The gear joint keeps pointers to the hinges and reads them every simulation step, so
+    /// the hinges must outlive the gear joint, or be replaced before they are dropped.
+    unsafe fn set_hinges(&mut self, hinge0: &impl HingeJoint, hinge1: &impl HingeJoint) -> bool {
+        unsafe {
+            PxGearJoint_setHinges_mut(
+                self.as_mut_ptr(),
+                Class::<PxJoint>::as_ptr(hinge0) as *const PxBase,
+                Class::<PxJoint>::as_ptr(hinge1) as *const PxBase,
+            )
+        }
+    }
+
+    /// Set the gear ratio, the angle of the second hinge is the ratio times the angle of the first.
+    fn set_gear_ratio(&mut self, ratio: f32) {
+        unsafe { PxGearJoint_setGearRatio_mut(self.as_mut_ptr(), ratio) }
+    }
+
+    /// Get the gear ratio.
+    fn get_gear_ratio(&self) -> f32 {
+        unsafe { PxGearJoint_getGearRatio(self.as_ptr()) }
+    }
+}
@@ -0,0 +1,72 @@
+use crate::{
+    joint::{Joint, LinearLimitPair},
+    traits::Class,
+};
+
+#[rustfmt::skip]
+use physx_sys::{
+    PxPrismaticJoint_getLimit,
+    PxPrismaticJoint_getPosition,
+    PxPrismaticJoint_getPrismaticJointFlags,
+    PxPrismaticJoint_getVelocity,
+    PxPrismaticJoint_setLimit_mut,
+    PxPrismaticJoint_setPrismaticJointFlag_mut,
+    PxPrismaticJoint_setPrismaticJointFlags_mut,
+};
+
+#[rustfmt::skip]
+pub use physx_sys::{
+    PxPrismaticJointFlag as PrismaticJointFlag,
+    PxPrismaticJointFlags as PrismaticJointFlags,
+};
+
+crate::joint::DeclareJoint!(
+    /// A new type wrapper for PxPrismaticJoint.  Parametrized by its user data type.
+    PxPrismaticJoint: PrismaticJoint
+);
+
+/// A joint that allows sliding along a single axis, the x axis of the joint frames.
+pub trait PrismaticJoint: Class<physx_sys::PxPrismaticJoint> + Joint {
+    crate::joint::JointConstructors!(
+        "prismatic",
+        PxPrismaticJoint,
+        phys_PxPrismaticJointCreate,
+        PxPrismaticJoint_getConcreteTypeName
+    );
+    crate::joint::JointToWorldConstructor!("prismatic", phys_PxPrismaticJointCreate);
+
+    /// Get the position of the joint along its axis.
+    fn get_position(&self) -> f32 {
+        unsafe { PxPrismaticJoint_getPosition(self.as_ptr()) }
+    }
+
+    /// Get the velocity of the joint along its axis.
+    fn get_velocity(&self) -> f32 {
+        unsafe { PxPrismaticJoint_getVelocity(self.as_ptr()) }
+    }
+
+    /// Set the linear limit of the joint.  Only used if `PrismaticJointFlag::LimitEnabled` is set.
+    fn set_limit(&mut self, limit: LinearLimitPair) {
+        unsafe { PxPrismaticJoint_setLimit_mut(self.as_mut_ptr(), &limit.into()) }
+    }
+
+    /// Get the linear limit of the joint.
+    fn get_limit(&self) -> LinearLimitPair {
+        unsafe { PxPrismaticJoint_getLimit(self.as_ptr()) }.into()
+    }
+
+    /// Set all the flags of the joint.
+    fn set_prismatic_joint_flags(&mut self, flags: PrismaticJointFlags) {
+        unsafe { PxPrismaticJoint_setPrismaticJointFlags_mut(self.as_mut_ptr(), flags) }
+    }
+
+    /// Set a single flag of the joint.
+    fn set_prismatic_joint_flag(&mut self, flag: PrismaticJointFlag, value: bool) {
+        unsafe { PxPrismaticJoint_setPrismaticJointFlag_mut(self.as_mut_ptr(), flag, value) }
+    }
+
+    /// Get the flags of the joint.
+    fn get_prismatic_joint_flags(&self) -> PrismaticJointFlags {
+        unsafe { PxPrismaticJoint_getPrismaticJointFlags(self.as_ptr()) }
+    }
+}
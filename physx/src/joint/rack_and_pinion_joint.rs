@@ -0,0 +1,77 @@
+use crate::{
+    joint::{HingeJoint, Joint, PxD6Joint, PxPrismaticJoint},
+    traits::Class,
+};
+
+#[rustfmt::skip]
+use physx_sys::{
+    PxBase,
+    PxJoint,
+    PxRackAndPinionJoint_getRatio,
+    PxRackAndPinionJoint_setData_mut,
+    PxRackAndPinionJoint_setJoints_mut,
+    PxRackAndPinionJoint_setRatio_mut,
+};
+
+crate::joint::DeclareJoint!(
+    /// A new type wrapper for PxRackAndPinionJoint.  Parametrized by its user data type.
+    PxRackAndPinionJoint: RackAndPinionJoint
+);
+
+/// Joints translating along a single axis, which can be the rack of a rack and pinion joint.
+/// Implemented by prismatic joints, and D6 joints with a single free linear axis.
+pub trait SliderJoint: Joint {}
+
+impl<U> SliderJoint for PxPrismaticJoint<U> {}
+impl<U> SliderJoint for PxD6Joint<U> {}
+
+/// A joint that couples the rotation of a hinge joint to the translation of a slider joint.
+///
+/// The actors of the rack and pinion joint must be the actors the two joints move.
+pub trait RackAndPinionJoint: Class<physx_sys::PxRackAndPinionJoint> + Joint {
+    crate::joint::JointConstructors!(
+        "rack and pinion",
+        PxRackAndPinionJoint,
+        phys_PxRackAndPinionJointCreate,
+        PxRackAndPinionJoint_getConcreteTypeName
+    );
+
+    /// Set the hinge joint of the pinion and the slider joint of the rack.  Returns false
+    /// if PhysX rejects them.
+    ///
+    /// # Safety
+    /// The rack and pinion joint keeps pointers to the joints and reads them every simulation
+    /// step, so they must outlive it, or be replaced before they are dropped.
+    unsafe fn set_joints(&mut self, hinge: &impl HingeJoint, slider: &impl SliderJoint) -> bool {
+        unsafe {
+            PxRackAndPinionJoint_setJoints_mut(
+                self.as_mut_ptr(),
+                Class::<PxJoint>::as_ptr(hinge) as *const PxBase,
+                Class::<PxJoint>::as_ptr(slider) as *const PxBase,
+            )
+        }
+    }
+
+    /// Set the ratio between the translation of the rack and the rotation of the pinion.
+    fn set_ratio(&mut self, ratio: f32) {
+        unsafe { PxRackAndPinionJoint_setRatio_mut(self.as_mut_ptr(), ratio) }
+    }
+
+    /// Get the ratio between the translation of the rack and the rotation of the pinion.
+    fn get_ratio(&self) -> f32 {
+        unsafe { PxRackAndPinionJoint_getRatio(self.as_ptr()) }
+    }
+
+    /// Set the ratio from the number of teeth on the rack and pinion, and the length of the rack.
+    /// Returns false if the data is invalid.
+    fn set_data(&mut self, nb_rack_teeth: u32, nb_pinion_teeth: u32, rack_length: f32) -> bool {
+        unsafe {
+            PxRackAndPinionJoint_setData_mut(
+                self.as_mut_ptr(),
+                nb_rack_teeth,
+                nb_pinion_teeth,
+                rack_length,
+            )
+        }
+    }
+}
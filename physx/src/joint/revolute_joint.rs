@@ -0,0 +1,108 @@
+use crate::{
+    joint::{AngularLimitPair, Joint},
+    traits::Class,
+};
+
+#[rustfmt::skip]
+use physx_sys::{
+    PxRevoluteJoint_getAngle,
+    PxRevoluteJoint_getDriveForceLimit,
+    PxRevoluteJoint_getDriveGearRatio,
+    PxRevoluteJoint_getDriveVelocity,
+    PxRevoluteJoint_getLimit,
+    PxRevoluteJoint_getRevoluteJointFlags,
+    PxRevoluteJoint_getVelocity,
+    PxRevoluteJoint_setDriveForceLimit_mut,
+    PxRevoluteJoint_setDriveGearRatio_mut,
+    PxRevoluteJoint_setDriveVelocity_mut,
+    PxRevoluteJoint_setLimit_mut,
+    PxRevoluteJoint_setRevoluteJointFlag_mut,
+    PxRevoluteJoint_setRevoluteJointFlags_mut,
+};
+
+#[rustfmt::skip]
+pub use physx_sys::{
+    PxRevoluteJointFlag as RevoluteJointFlag,
+    PxRevoluteJointFlags as RevoluteJointFlags,
+};
+
+crate::joint::DeclareJoint!(
+    /// A new type wrapper for PxRevoluteJoint.  Parametrized by its user data type.
+    PxRevoluteJoint: RevoluteJoint
+);
+
+/// A joint that allows rotation around a single axis, the x axis of the joint frames.
+pub trait RevoluteJoint: Class<physx_sys::PxRevoluteJoint> + Joint {
+    crate::joint::JointConstructors!(
+        "revolute",
+        PxRevoluteJoint,
+        phys_PxRevoluteJointCreate,
+        PxRevoluteJoint_getConcreteTypeName
+    );
+    crate::joint::JointToWorldConstructor!("revolute", phys_PxRevoluteJointCreate);
+
+    /// Get the angle of the joint, in the range (-2*Pi, 2*Pi].
+    fn get_angle(&self) -> f32 {
+        unsafe { PxRevoluteJoint_getAngle(self.as_ptr()) }
+    }
+
+    /// Get the angular velocity of the joint.
+    fn get_velocity(&self) -> f32 {
+        unsafe { PxRevoluteJoint_getVelocity(self.as_ptr()) }
+    }
+
+    /// Set the angular limit of the joint.  Only used if `RevoluteJointFlag::LimitEnabled` is set.
+    fn set_limit(&mut self, limit: AngularLimitPair) {
+        unsafe { PxRevoluteJoint_setLimit_mut(self.as_mut_ptr(), &limit.into()) }
+    }
+
+    /// Get the angular limit of the joint.
+    fn get_limit(&self) -> AngularLimitPair {
+        unsafe { PxRevoluteJoint_getLimit(self.as_ptr()) }.into()
+    }
+
+    /// Set the target velocity of the drive.  Only used if `RevoluteJointFlag::DriveEnabled` is set.
+    fn set_drive_velocity(&mut self, velocity: f32, autowake: bool) {
+        unsafe { PxRevoluteJoint_setDriveVelocity_mut(self.as_mut_ptr(), velocity, autowake) }
+    }
+
+    /// Get the target velocity of the drive.
+    fn get_drive_velocity(&self) -> f32 {
+        unsafe { PxRevoluteJoint_getDriveVelocity(self.as_ptr()) }
+    }
+
+    /// Set the maximum torque the drive can exert.
+    fn set_drive_force_limit(&mut self, limit: f32) {
+        unsafe { PxRevoluteJoint_setDriveForceLimit_mut(self.as_mut_ptr(), limit) }
+    }
+
+    /// Get the maximum torque the drive can exert.
+    fn get_drive_force_limit(&self) -> f32 {
+        unsafe { PxRevoluteJoint_getDriveForceLimit(self.as_ptr()) }
+    }
+
+    /// Set the gear ratio of the drive.
+    fn set_drive_gear_ratio(&mut self, ratio: f32) {
+        unsafe { PxRevoluteJoint_setDriveGearRatio_mut(self.as_mut_ptr(), ratio) }
+    }
+
+    /// Get the gear ratio of the drive.
+    fn get_drive_gear_ratio(&self) -> f32 {
+        unsafe { PxRevoluteJoint_getDriveGearRatio(self.as_ptr()) }
+    }
+
+    /// Set all the flags of the joint.
+    fn set_revolute_joint_flags(&mut self, flags: RevoluteJointFlags) {
+        unsafe { PxRevoluteJoint_setRevoluteJointFlags_mut(self.as_mut_ptr(), flags) }
+    }
+
+    /// Set a single flag of the joint.
+    fn set_revolute_joint_flag(&mut self, flag: RevoluteJointFlag, value: bool) {
+        unsafe { PxRevoluteJoint_setRevoluteJointFlag_mut(self.as_mut_ptr(), flag, value) }
+    }
+
+    /// Get the flags of the joint.
+    fn get_revolute_joint_flags(&self) -> RevoluteJointFlags {
+        unsafe { PxRevoluteJoint_getRevoluteJointFlags(self.as_ptr()) }
+    }
+}
@@ -0,0 +1,72 @@
+use crate::{
+    joint::{Joint, LimitCone},
+    traits::Class,
+};
+
+#[rustfmt::skip]
+use physx_sys::{
+    PxSphericalJoint_getLimitCone,
+    PxSphericalJoint_getSphericalJointFlags,
+    PxSphericalJoint_getSwingYAngle,
+    PxSphericalJoint_getSwingZAngle,
+    PxSphericalJoint_setLimitCone_mut,
+    PxSphericalJoint_setSphericalJointFlag_mut,
+    PxSphericalJoint_setSphericalJointFlags_mut,
+};
+
+#[rustfmt::skip]
+pub use physx_sys::{
+    PxSphericalJointFlag as SphericalJointFlag,
+    PxSphericalJointFlags as SphericalJointFlags,
+};
+
+crate::joint::DeclareJoint!(
+    /// A new type wrapper for PxSphericalJoint.  Parametrized by its user data type.
+    PxSphericalJoint: SphericalJoint
+);
+
+/// A joint that allows free rotation around a point, like a ball and socket.
+pub trait SphericalJoint: Class<physx_sys::PxSphericalJoint> + Joint {
+    crate::joint::JointConstructors!(
+        "spherical",
+        PxSphericalJoint,
+        phys_PxSphericalJointCreate,
+        PxSphericalJoint_getConcreteTypeName
+    );
+    crate::joint::JointToWorldConstructor!("spherical", phys_PxSphericalJointCreate);
+
+    /// Set the cone limit of the joint.  Only used if `SphericalJointFlag::LimitEnabled` is set.
+    fn set_limit_cone(&mut self, limit: LimitCone) {
+        unsafe { PxSphericalJoint_setLimitCone_mut(self.as_mut_ptr(), &limit.into()) }
+    }
+
+    /// Get the cone limit of the joint.
+    fn get_limit_cone(&self) -> LimitCone {
+        unsafe { PxSphericalJoint_getLimitCone(self.as_ptr()) }.into()
+    }
+
+    /// Get the swing angle of the joint around the y axis.
+    fn get_swing_y_angle(&self) -> f32 {
+        unsafe { PxSphericalJoint_getSwingYAngle(self.as_ptr()) }
+    }
+
+    /// Get the swing angle of the joint around the z axis.
+    fn get_swing_z_angle(&self) -> f32 {
+        unsafe { PxSphericalJoint_getSwingZAngle(self.as_ptr()) }
+    }
+
+    /// Set all the flags of the joint.
+    fn set_spherical_joint_flags(&mut self, flags: SphericalJointFlags) {
+        unsafe { PxSphericalJoint_setSphericalJointFlags_mut(self.as_mut_ptr(), flags) }
+    }
+
+    /// Set a single flag of the joint.
+    fn set_spherical_joint_flag(&mut self, flag: SphericalJointFlag, value: bool) {
+        unsafe { PxSphericalJoint_setSphericalJointFlag_mut(self.as_mut_ptr(), flag, value) }
+    }
+
+    /// Get the flags of the joint.
+    fn get_spherical_joint_flags(&self) -> SphericalJointFlags {
+        unsafe { PxSphericalJoint_getSphericalJointFlags(self.as_ptr()) }
+    }
+}
@@ -0,0 +1,163 @@
+use crate::math::{PxTransform, PxVec3};
+
+use std::ffi::c_void;
+
+#[rustfmt::skip]
+use physx_sys::{
+    create_constraint_visualizer,
+    destroy_constraint_visualizer,
+    ConstraintVisualizerCallbacks,
+    PxConstraintVisualizer,
+};
+
+bitflags::bitflags! {
+    /// Which parts of a joint to emit into a [`ConstraintVisualizer`].
+    #[repr(transparent)]
+    pub struct ConstraintVisualizationFlags: u32 {
+        /// Emit the joint frames of both actors
+        const LOCAL_FRAMES = 1 << 0;
+        /// Emit the enabled limits of the joint
+        const LIMITS = 1 << 1;
+    }
+}
+
+/// Receives the frames and limits of joints, see [`Joint::visualize`](crate::joint::Joint::visualize).
+/// All transforms are in world space and all angles are in radians.  Every method does nothing by
+/// default, so only the shapes that are drawn need to be implemented.
+#[allow(unused_variables)]
+pub trait ConstraintVisualizer {
+    /// The joint frames of the two actors.
+    fn visualize_joint_frames(&mut self, parent: &PxTransform, child: &PxTransform) {}
+
+    /// A linear limit of `value` between two frames, `active` if the limit is being enforced.
+    fn visualize_linear_limit(
+        &mut self,
+        t0: &PxTransform,
+        t1: &PxTransform,
+        value: f32,
+        active: bool,
+    ) {
+    }
+
+    /// An angular limit between `lower` and `upper` around the x axis of `t0`.
+    fn visualize_angular_limit(&mut self, t0: &PxTransform, lower: f32, upper: f32, active: bool) {}
+
+    /// An elliptical cone limit around the x axis of `t`.  The angles are given as the tangents of
+    /// a quarter of the swing limits.
+    fn visualize_limit_cone(
+        &mut self,
+        t: &PxTransform,
+        tan_q_swing_y: f32,
+        tan_q_swing_z: f32,
+        active: bool,
+    ) {
+    }
+
+    /// A double cone limit with half angle `angle` around the x axis of `t`.
+    fn visualize_double_cone(&mut self, t: &PxTransform, angle: f32, active: bool) {}
+
+    /// A line with a color in the `0xAARRGGBB` format.
+    fn visualize_line(&mut self, p0: &PxVec3, p1: &PxVec3, color: u32) {}
+}
+
+/// Owns a PxConstraintVisualizer that forwards to a [`ConstraintVisualizer`] for its lifetime.
+pub(crate) struct VisualizerCallback(*mut PxConstraintVisualizer);
+
+impl VisualizerCallback {
+    pub(crate) fn new<V: ConstraintVisualizer>(visualizer: &mut V) -> Self {
+        let callbacks = ConstraintVisualizerCallbacks {
+            visualize_joint_frames: visualize_joint_frames_shim::<V>,
+            visualize_linear_limit: visualize_linear_limit_shim::<V>,
+            visualize_angular_limit: visualize_angular_limit_shim::<V>,
+            visualize_limit_cone: visualize_limit_cone_shim::<V>,
+            visualize_double_cone: visualize_double_cone_shim::<V>,
+            visualize_line: visualize_line_shim::<V>,
+            user_data: visualizer as *mut V as *mut c_void,
+        };
+        Self(unsafe { create_constraint_visualizer(&callbacks) })
+    }
+
+    pub(crate) fn as_mut_ptr(&mut self) -> *mut PxConstraintVisualizer {
+        self.0
+    }
+}
+
+impl Drop for VisualizerCallback {
+    fn drop(&mut self) {
+        unsafe { destroy_constraint_visualizer(self.0) }
+    }
+}
+
+unsafe extern "C" fn visualize_joint_frames_shim<V: ConstraintVisualizer>(
+    user_data: *mut c_void,
+    parent: *const physx_sys::PxTransform,
+    child: *const physx_sys::PxTransform,
+) {
+    unsafe {
+        let visualizer = &mut *(user_data as *mut V);
+        visualizer.visualize_joint_frames(&(*parent).into(), &(*child).into());
+    }
+}
+
+unsafe extern "C" fn visualize_linear_limit_shim<V: ConstraintVisualizer>(
+    user_data: *mut c_void,
+    t0: *const physx_sys::PxTransform,
+    t1: *const physx_sys::PxTransform,
+    value: f32,
+    active: bool,
+) {
+    unsafe {
+        let visualizer = &mut *(user_data as *mut V);
+        visualizer.visualize_linear_limit(&(*t0).into(), &(*t1).into(), value, active);
+    }
+}
+
+unsafe extern "C" fn visualize_angular_limit_shim<V: ConstraintVisualizer>(
+    user_data: *mut c_void,
+    t0: *const physx_sys::PxTransform,
+    lower: f32,
+    upper: f32,
+    active: bool,
+) {
+    unsafe {
+        let visualizer = &mut *(user_data as *mut V);
+        visualizer.visualize_angular_limit(&(*t0).into(), lower, upper, active);
+    }
+}
+
+unsafe extern "C" fn visualize_limit_cone_shim<V: ConstraintVisualizer>(
+    user_data: *mut c_void,
+    t: *const physx_sys::PxTransform,
+    tan_q_swing_y: f32,
+    tan_q_swing_z: f32,
+    active: bool,
+) {
+    unsafe {
+        let visualizer = &mut *(user_data as *mut V);
+        visualizer.visualize_limit_cone(&(*t).into(), tan_q_swing_y, tan_q_swing_z, active);
+    }
+}
+
+unsafe extern "C" fn visualize_double_cone_shim<V: ConstraintVisualizer>(
+    user_data: *mut c_void,
+    t: *const physx_sys::PxTransform,
+    angle: f32,
+    active: bool,
+) {
+    unsafe {
+        let visualizer = &mut *(user_data as *mut V);
+        visualizer.visualize_double_cone(&(*t).into(), angle, active);
+    }
+}
+
+unsafe extern "C" fn visualize_line_shim<V: ConstraintVisualizer>(
+    user_data: *mut c_void,
+    p0: *const physx_sys::PxVec3,
+    p1: *const physx_sys::PxVec3,
+    color: u32,
+) {
+    unsafe {
+        let visualizer = &mut *(user_data as *mut V);
+        visualizer.visualize_line(&(*p0).into(), &(*p1).into(), color);
+    }
+}
@@ -113,10 +113,14 @@ pub mod articulation_cache;
 pub mod articulation_joint_reduced_coordinate;
 pub mod articulation_link;
 pub mod articulation_reduced_coordinate;
+pub mod batch_query;
 pub mod base;
 pub mod bvh;
+pub mod collection;
 pub mod constraint;
+pub mod contact_modify_callback;
 pub mod controller;
+pub mod controller_callbacks;
 pub mod controller_manager;
 pub mod convex_mesh;
 pub mod cooking;
@@ -124,19 +128,24 @@ pub mod cuda_context_manager;
 pub mod foundation;
 pub mod geometry;
 pub mod height_field;
+pub mod joint;
 pub mod material;
+pub mod obstacle_context;
 pub mod owner;
 pub mod particle_and_diffuse_buffer;
 pub mod particle_buffer;
 pub mod physics;
 pub mod pruning_structure;
+pub mod render_buffer;
 pub mod rigid_actor;
 pub mod rigid_body;
 pub mod rigid_dynamic;
 pub mod rigid_static;
 pub mod scene;
+pub mod scene_query;
 pub mod shape;
 pub mod simulation_event_callback;
+pub(crate) mod stream;
 pub mod triangle_mesh;
 pub mod visual_debugger;
 
@@ -0,0 +1,188 @@
+use crate::{
+    math::{PxExtendedVec3, PxQuat, PxVec3},
+    traits::Class,
+};
+
+use std::ffi::c_void;
+
+#[rustfmt::skip]
+use physx_sys::{
+    PxBoxObstacle,
+    PxBoxObstacle_new,
+    PxCapsuleObstacle,
+    PxCapsuleObstacle_new,
+    PxGeometryType,
+    PxObstacle,
+    PxObstacleContext_addObstacle_mut,
+    PxObstacleContext_getNbObstacles,
+    PxObstacleContext_getObstacleByHandle,
+    PxObstacleContext_removeObstacle_mut,
+    PxObstacleContext_updateObstacle_mut,
+    PxObstacle_getType,
+};
+
+/// Handle returned by PhysX for obstacles that could not be added, or when no obstacle was touched.
+pub(crate) const INVALID_OBSTACLE_HANDLE: u32 = 0xffff_ffff;
+
+/// Identifies an obstacle in an [`ObstacleContext`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct ObstacleHandle(pub(crate) u32);
+
+impl ObstacleHandle {
+    pub(crate) fn from_raw(handle: u32) -> Option<Self> {
+        (handle != INVALID_OBSTACLE_HANDLE).then_some(Self(handle))
+    }
+}
+
+/// A box blocking controllers.
+#[derive(Copy, Clone)]
+pub struct BoxObstacle {
+    /// World space position of the center of the box.
+    pub position: PxExtendedVec3,
+    /// World space rotation of the box.
+    pub rotation: PxQuat,
+    pub half_extents: PxVec3,
+    /// Reported in controller hits and passed to behavior callbacks.
+    pub user_data: usize,
+}
+
+/// A capsule blocking controllers, along the x axis before rotation.
+#[derive(Copy, Clone)]
+pub struct CapsuleObstacle {
+    /// World space position of the center of the capsule.
+    pub position: PxExtendedVec3,
+    /// World space rotation of the capsule.
+    pub rotation: PxQuat,
+    /// Half of the distance between the centers of the end caps.
+    pub half_height: f32,
+    pub radius: f32,
+    /// Reported in controller hits and passed to behavior callbacks.
+    pub user_data: usize,
+}
+
+/// An obstacle blocking controllers that is not an actor of the scene, for example an NPC
+/// or a door moved by gameplay code.
+#[derive(Copy, Clone)]
+pub enum Obstacle {
+    Box(BoxObstacle),
+    Capsule(CapsuleObstacle),
+}
+
+impl From<BoxObstacle> for Obstacle {
+    fn from(obstacle: BoxObstacle) -> Self {
+        Obstacle::Box(obstacle)
+    }
+}
+
+impl From<CapsuleObstacle> for Obstacle {
+    fn from(obstacle: CapsuleObstacle) -> Self {
+        Obstacle::Capsule(obstacle)
+    }
+}
+
+impl Obstacle {
+    /// Call `f` with the obstacle converted to a PxBoxObstacle or PxCapsuleObstacle.
+    fn with_raw<R>(&self, f: impl FnOnce(*const PxObstacle) -> R) -> R {
+        unsafe {
+            match self {
+                Obstacle::Box(obstacle) => {
+                    let mut raw: PxBoxObstacle = PxBoxObstacle_new();
+                    raw.mUserData = obstacle.user_data as *mut c_void;
+                    raw.mPos = obstacle.position.into();
+                    raw.mRot = obstacle.rotation.into();
+                    raw.mHalfExtents = obstacle.half_extents.into();
+                    f(&raw as *const PxBoxObstacle as *const PxObstacle)
+                }
+                Obstacle::Capsule(obstacle) => {
+                    let mut raw: PxCapsuleObstacle = PxCapsuleObstacle_new();
+                    raw.mUserData = obstacle.user_data as *mut c_void;
+                    raw.mPos = obstacle.position.into();
+                    raw.mRot = obstacle.rotation.into();
+                    raw.mHalfHeight = obstacle.half_height;
+                    raw.mRadius = obstacle.radius;
+                    f(&raw as *const PxCapsuleObstacle as *const PxObstacle)
+                }
+            }
+        }
+    }
+
+    /// # Safety
+    /// `obstacle` must point to a PxBoxObstacle or PxCapsuleObstacle.
+    unsafe fn from_raw(obstacle: &PxObstacle) -> Option<Self> {
+        unsafe {
+            match PxObstacle_getType(obstacle) {
+                PxGeometryType::Box => {
+                    let raw = &*(obstacle as *const PxObstacle as *const PxBoxObstacle);
+                    Some(Obstacle::Box(BoxObstacle {
+                        position: raw.mPos.into(),
+                        rotation: raw.mRot.into(),
+                        half_extents: raw.mHalfExtents.into(),
+                        user_data: raw.mUserData as usize,
+                    }))
+                }
+                PxGeometryType::Capsule => {
+                    let raw = &*(obstacle as *const PxObstacle as *const PxCapsuleObstacle);
+                    Some(Obstacle::Capsule(CapsuleObstacle {
+                        position: raw.mPos.into(),
+                        rotation: raw.mRot.into(),
+                        half_height: raw.mHalfHeight,
+                        radius: raw.mRadius,
+                        user_data: raw.mUserData as usize,
+                    }))
+                }
+                _ => None,
+            }
+        }
+    }
+}
+
+/// A set of obstacles controllers collide with when it is passed to
+/// [`ControllerFilters::with_obstacles`](crate::controller::ControllerFilters::with_obstacles).
+///
+/// Created by [`ControllerManager::create_obstacle_context`](crate::controller_manager::ControllerManager::create_obstacle_context),
+/// which owns the context and releases it along with the controllers.
+#[repr(transparent)]
+pub struct ObstacleContext {
+    obj: physx_sys::PxObstacleContext,
+}
+
+crate::DeriveClassForNewType!(ObstacleContext: PxObstacleContext);
+
+impl ObstacleContext {
+    /// Add an obstacle.  Returns `None` if it could not be added.
+    pub fn add_obstacle(&mut self, obstacle: &Obstacle) -> Option<ObstacleHandle> {
+        let handle = obstacle
+            .with_raw(|raw| unsafe { PxObstacleContext_addObstacle_mut(self.as_mut_ptr(), raw) });
+        ObstacleHandle::from_raw(handle)
+    }
+
+    /// Replace an obstacle, for example to move it.  Returns false if there is no obstacle
+    /// with this handle, or if it is not of the same kind.
+    pub fn update_obstacle(&mut self, handle: ObstacleHandle, obstacle: &Obstacle) -> bool {
+        obstacle.with_raw(|raw| unsafe {
+            PxObstacleContext_updateObstacle_mut(self.as_mut_ptr(), handle.0, raw)
+        })
+    }
+
+    /// Remove an obstacle.  Returns false if there is no obstacle with this handle.
+    pub fn remove_obstacle(&mut self, handle: ObstacleHandle) -> bool {
+        unsafe { PxObstacleContext_removeObstacle_mut(self.as_mut_ptr(), handle.0) }
+    }
+
+    /// Get the number of obstacles in the context.
+    pub fn get_nb_obstacles(&self) -> u32 {
+        unsafe { PxObstacleContext_getNbObstacles(self.as_ptr()) }
+    }
+
+    /// Get an obstacle by handle.
+    pub fn get_obstacle(&self, handle: ObstacleHandle) -> Option<Obstacle> {
+        unsafe {
+            Obstacle::from_raw(
+                PxObstacleContext_getObstacleByHandle(self.as_ptr(), handle.0).as_ref()?,
+            )
+        }
+    }
+}
+
+unsafe impl Send for ObstacleContext {}
+unsafe impl Sync for ObstacleContext {}
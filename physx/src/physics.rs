@@ -16,6 +16,7 @@ use crate::{
     },
     bvh::Bvh,
     constraint::Constraint,
+    contact_modify_callback::ContactModifyCallback,
     convex_mesh::ConvexMesh,
     foundation::{AllocatorCallback, DefaultAllocator, Foundation, PxFoundation},
     geometry::Geometry,
@@ -30,6 +31,7 @@ use crate::{
     scene::PxScene,
     shape::{Shape, ShapeFlags},
     simulation_event_callback::*,
+    stream::read_from_slice,
     traits::{Class, Descriptor, SceneDescriptor, UserData},
     triangle_mesh::TriangleMesh,
     visual_debugger::VisualDebugger,
@@ -296,20 +298,21 @@ pub trait Physics: Class<physx_sys::PxPhysics> + Sized {
 
     /// Create a new scene with from a descriptor.
     #[allow(clippy::type_complexity)]
-    fn create_scene<U, L, S, D, C, OC, OT, OCB, OWS, OA>(
+    fn create_scene<U, L, S, D, C, OC, OT, OCB, OWS, OA, OCM>(
         &mut self,
-        scene_descriptor: SceneDescriptor<U, L, S, D, C, OC, OT, OCB, OWS, OA>,
-    ) -> Option<Owner<PxScene<U, L, S, D, C, OC, OT, OCB, OWS, OA>>>
+        scene_descriptor: SceneDescriptor<U, L, S, D, C, OC, OT, OCB, OWS, OA, OCM>,
+    ) -> Option<Owner<PxScene<U, L, S, D, C, OC, OT, OCB, OWS, OA, OCM>>>
     where
         L: ArticulationLink,
         S: RigidStatic,
         D: RigidDynamic,
         C: ArticulationReducedCoordinate,
-        OC: CollisionCallback,
+        OC: CollisionCallback<L, S, D>,
         OT: TriggerCallback,
         OCB: ConstraintBreakCallback,
         OWS: WakeSleepCallback<L, S, D>,
         OA: AdvanceCallback<L, D>,
+        OCM: ContactModifyCallback<L, S, D>,
     {
         scene_descriptor.create(self)
     }
@@ -358,6 +361,20 @@ pub trait Physics: Class<physx_sys::PxPhysics> + Sized {
         unsafe { Bvh::from_raw(PxPhysics_createBVH_mut(self.as_mut_ptr(), stream)) }
     }
 
+    /// Load a BVH cooked with [`cook_bvh_to_vec`](crate::cooking::cook_bvh_to_vec).
+    ///
+    /// # Safety
+    /// PhysX does not validate the contents of the data, such as indices and counts.  It must
+    /// have been produced by `cook_bvh_to_vec` with the same version of PhysX.
+    unsafe fn load_bvh_from_bytes(&mut self, data: &[u8]) -> Option<Owner<Bvh>> {
+        read_from_slice(data, |input| unsafe {
+            Bvh::from_raw(PxPhysics_createBVH_mut(
+                self.as_mut_ptr(),
+                input as *mut PxInputStream,
+            ))
+        })
+    }
+
     /// Create a new constraint.  The constraint class-trait is not implemented yet.
     fn create_constraint(
         &mut self,
@@ -384,11 +401,41 @@ pub trait Physics: Class<physx_sys::PxPhysics> + Sized {
         unsafe { ConvexMesh::from_raw(PxPhysics_createConvexMesh_mut(self.as_mut_ptr(), stream)) }
     }
 
+    /// Load a convex mesh cooked with
+    /// [`cook_convex_mesh_to_vec`](crate::cooking::cook_convex_mesh_to_vec).
+    ///
+    /// # Safety
+    /// PhysX does not validate the contents of the data, such as indices and counts.  It must
+    /// have been produced by `cook_convex_mesh_to_vec` with the same version of PhysX.
+    unsafe fn load_convex_mesh_from_bytes(&mut self, data: &[u8]) -> Option<Owner<ConvexMesh>> {
+        read_from_slice(data, |input| unsafe {
+            ConvexMesh::from_raw(PxPhysics_createConvexMesh_mut(
+                self.as_mut_ptr(),
+                input as *mut PxInputStream,
+            ))
+        })
+    }
+
     /// Create a new height field.
     fn create_height_field(&mut self, stream: &mut PxInputStream) -> Option<Owner<HeightField>> {
         unsafe { HeightField::from_raw(PxPhysics_createHeightField_mut(self.as_mut_ptr(), stream)) }
     }
 
+    /// Load a height field cooked with
+    /// [`cook_height_field_to_vec`](crate::cooking::cook_height_field_to_vec).
+    ///
+    /// # Safety
+    /// PhysX does not validate the contents of the data, such as indices and counts.  It must
+    /// have been produced by `cook_height_field_to_vec` with the same version of PhysX.
+    unsafe fn load_height_field_from_bytes(&mut self, data: &[u8]) -> Option<Owner<HeightField>> {
+        read_from_slice(data, |input| unsafe {
+            HeightField::from_raw(PxPhysics_createHeightField_mut(
+                self.as_mut_ptr(),
+                input as *mut PxInputStream,
+            ))
+        })
+    }
+
     /// Create a new material with ref count set to one.
     fn create_material(
         &mut self,
@@ -485,6 +532,21 @@ pub trait Physics: Class<physx_sys::PxPhysics> + Sized {
         }
     }
 
+    /// Load a triangle mesh cooked with
+    /// [`cook_triangle_mesh_to_vec`](crate::cooking::cook_triangle_mesh_to_vec).
+    ///
+    /// # Safety
+    /// PhysX does not validate the contents of the data, such as indices and counts.  It must
+    /// have been produced by `cook_triangle_mesh_to_vec` with the same version of PhysX.
+    unsafe fn load_triangle_mesh_from_bytes(&mut self, data: &[u8]) -> Option<Owner<TriangleMesh>> {
+        read_from_slice(data, |input| unsafe {
+            TriangleMesh::from_raw(PxPhysics_createTriangleMesh_mut(
+                self.as_mut_ptr(),
+                input as *mut PxInputStream,
+            ))
+        })
+    }
+
     /// Create a new rigid dynamic actor.
     fn create_rigid_dynamic<U>(
         &mut self,
@@ -11,14 +11,24 @@ pub use crate::articulation_reduced_coordinate::{
     ArticulationFlag, ArticulationFlags, ArticulationReducedCoordinate,
 };
 pub use crate::base::{Base, BaseFlag, ConcreteType};
-pub use crate::controller::Controller;
+pub use crate::batch_query::{BatchQuery, BatchQueryLimits, BatchQueryResults};
+pub use crate::contact_modify_callback::{
+    ContactModifyCallback, ContactModifyPair, ContactSet, NoContactModify,
+};
+pub use crate::controller::{Controller, ControllerDescriptor};
+pub use crate::controller_callbacks::{ControllerBehavior, ControllerHitReport};
 pub use crate::controller_manager::ControllerManager;
 pub use crate::foundation::{AllocatorCallback, Foundation, ScratchBuffer};
 pub use crate::geometry::*;
 pub use crate::height_field::*;
+pub use crate::joint::{
+    ConstraintVisualizer, ContactJoint, D6Joint, DistanceJoint, FixedJoint, GearJoint, Joint,
+    PrismaticJoint, RackAndPinionJoint, RevoluteJoint, SphericalJoint,
+};
 pub use crate::math::*;
 pub use crate::owner::Owner;
 pub use crate::physics::{Physics, PhysicsFoundation, PX_PHYSICS_VERSION};
+pub use crate::render_buffer::{RenderBuffer, VisualizationParameter};
 pub use crate::rigid_actor::RigidActor;
 pub use crate::rigid_body::{ForceMode, RigidBody, RigidBodyFlag, RigidBodyFlags};
 pub use crate::rigid_dynamic::{RigidDynamic, RigidDynamicLockFlag, RigidDynamicLockFlags};
@@ -27,10 +37,14 @@ pub use crate::scene::{
     BroadPhaseType, FilterShaderDescriptor, PairFilteringMode, PruningStructureType, Scene,
     SceneFlag, SimulationThreadType, SolverType,
 };
+pub use crate::scene_query::{
+    OverlapHit, PostFilterHit, QueryFilter, QueryFlag, QueryFlags, QueryHitType, QueryMode,
+    QueryOptions, RaycastHit, SweepHit,
+};
 pub use crate::shape::{CollisionLayers, Shape, ShapeFlag, ShapeFlags};
 pub use crate::simulation_event_callback::{
-    AdvanceCallback, CollisionCallback, ConstraintBreakCallback, PxSimulationEventCallback,
-    TriggerCallback, WakeSleepCallback,
+    AdvanceCallback, CollisionCallback, ConstraintBreakCallback, ContactPair, ContactPairHeader,
+    PxSimulationEventCallback, TriggerCallback, WakeSleepCallback,
 };
 pub use crate::traits::descriptor::{
     ConstraintDescriptor, MaterialDescriptor, PlaneDescriptor, RigidDynamicDescriptor,
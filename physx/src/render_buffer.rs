@@ -0,0 +1,90 @@
+use crate::traits::Class;
+
+use std::slice;
+
+#[rustfmt::skip]
+use physx_sys::{
+    PxRenderBuffer_clear_mut,
+    PxRenderBuffer_getLines,
+    PxRenderBuffer_getNbLines,
+    PxRenderBuffer_getNbPoints,
+    PxRenderBuffer_getNbTriangles,
+    PxRenderBuffer_getPoints,
+    PxRenderBuffer_getTriangles,
+};
+
+#[rustfmt::skip]
+pub use physx_sys::{
+    PxDebugColor as DebugColor,
+    PxDebugLine as DebugLine,
+    PxDebugPoint as DebugPoint,
+    PxDebugTriangle as DebugTriangle,
+    PxVisualizationParameter as VisualizationParameter,
+};
+
+/// A new type wrapper for `PxRenderBuffer`, the debug geometry produced by a scene
+/// during simulation, or by a controller manager when controllers move.  Colors are
+/// packed as `0xAARRGGBB`, see [`DebugColor`] for the colors PhysX uses.
+///
+/// A scene produces nothing unless [`VisualizationParameter::Scale`] and the parameters
+/// for the wanted geometry are set to non-zero values on it, and the actors or shapes
+/// have their visualization flag enabled.  A controller manager only produces the
+/// geometry enabled by its debug rendering flags, see
+/// [`ControllerDebugRenderFlags`](crate::controller_manager::ControllerDebugRenderFlags).
+#[repr(transparent)]
+pub struct RenderBuffer {
+    obj: physx_sys::PxRenderBuffer,
+}
+
+crate::DeriveClassForNewType!(RenderBuffer: PxRenderBuffer);
+
+impl RenderBuffer {
+    /// Get the debug points.
+    pub fn points(&self) -> &[DebugPoint] {
+        unsafe {
+            from_raw_parts(
+                PxRenderBuffer_getPoints(self.as_ptr()),
+                PxRenderBuffer_getNbPoints(self.as_ptr()),
+            )
+        }
+    }
+
+    /// Get the debug lines.
+    pub fn lines(&self) -> &[DebugLine] {
+        unsafe {
+            from_raw_parts(
+                PxRenderBuffer_getLines(self.as_ptr()),
+                PxRenderBuffer_getNbLines(self.as_ptr()),
+            )
+        }
+    }
+
+    /// Get the debug triangles.
+    pub fn triangles(&self) -> &[DebugTriangle] {
+        unsafe {
+            from_raw_parts(
+                PxRenderBuffer_getTriangles(self.as_ptr()),
+                PxRenderBuffer_getNbTriangles(self.as_ptr()),
+            )
+        }
+    }
+
+    /// Returns true if the buffer contains no geometry.
+    pub fn is_empty(&self) -> bool {
+        self.points().is_empty() && self.lines().is_empty() && self.triangles().is_empty()
+    }
+
+    /// Remove all the geometry from the buffer.
+    pub fn clear(&mut self) {
+        unsafe { PxRenderBuffer_clear_mut(self.as_mut_ptr()) }
+    }
+}
+
+/// The buffers are null while empty, which `slice::from_raw_parts` does not allow.
+unsafe fn from_raw_parts<'a, T>(ptr: *const T, len: u32) -> &'a [T] {
+    if ptr.is_null() || len == 0 {
+        &[]
+    } else {
+        unsafe { slice::from_raw_parts(ptr, len as usize) }
+    }
+}
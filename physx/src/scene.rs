@@ -9,16 +9,23 @@ use crate::{
     aggregate::{Aggregate, PxAggregate},
     articulation_link::ArticulationLink,
     articulation_reduced_coordinate::ArticulationReducedCoordinate,
+    collection::Collection,
     constraint::Constraint,
+    contact_modify_callback::{ContactModifyCallback, ContactModifyCallbackRaw, NoContactModify},
     controller::Controller,
     controller_manager::{ControllerManager, PxControllerManager},
     foundation::ScratchBuffer,
-    math::PxVec3,
+    geometry::Geometry,
+    math::{PxBounds3, PxTransform, PxVec3},
     owner::Owner,
     pruning_structure::PruningStructure,
+    render_buffer::{RenderBuffer, VisualizationParameter},
     rigid_actor::RigidActor,
     rigid_dynamic::RigidDynamic,
     rigid_static::RigidStatic,
+    scene_query::{
+        self, FilterCallback, OverlapHit, QueryFilter, QueryOptions, RaycastHit, SweepHit,
+    },
     simulation_event_callback::{
         AdvanceCallback, CollisionCallback, ConstraintBreakCallback, PxSimulationEventCallback,
         TriggerCallback, WakeSleepCallback,
@@ -42,6 +49,8 @@ use physx_sys::{
     PxBaseTask,
     PxBroadPhaseCallback,
     PxCCDContactModifyCallback,
+    PxConstraintExtIDs,
+    PxConstraint_getExternalReference_mut,
     PxContactModifyCallback,
     PxCpuDispatcher,
     PxSceneLimits,
@@ -50,6 +59,7 @@ use physx_sys::{
     PxScene_addActors_mut_1,
     PxScene_addAggregate_mut,
     PxScene_addArticulation_mut,
+    PxScene_addCollection_mut,
     PxScene_fetchResults_mut,
     PxScene_getActiveActors_mut,
     PxScene_getActors,
@@ -64,9 +74,12 @@ use physx_sys::{
     PxScene_getNbAggregates,
     PxScene_getNbArticulations,
     PxScene_getNbConstraints,
+    PxScene_getRenderBuffer_mut,
     PxScene_getScenePvdClient_mut,
     PxScene_getSimulationEventCallback,
     PxScene_getStaticKinematicFilteringMode,
+    PxScene_getVisualizationCullingBox,
+    PxScene_getVisualizationParameter,
     PxScene_release_mut,
     PxScene_removeActor_mut,
     PxScene_removeActors_mut,
@@ -78,6 +91,8 @@ use physx_sys::{
     PxScene_setCCDContactModifyCallback_mut,
     PxScene_setContactModifyCallback_mut,
     PxScene_setGravity_mut,
+    PxScene_setVisualizationCullingBox_mut,
+    PxScene_setVisualizationParameter_mut,
     PxScene_simulate_mut,
 };
 
@@ -145,39 +160,42 @@ impl Default for FilterShaderDescriptor {
 
 /// A new type wrapper for PxScene.  Parametrized by its user data type,
 /// the ArticulationLink, RigidStatic, and RigidDynamic actors, Articulation, and
-/// ArticulationReducedCoordinate articulations, and Collision, Trigger, ConstraintBreak,
-/// WakeSleep and Advance Callbacks.  Due to the number of generic type parameters,
+/// ArticulationReducedCoordinate articulations, Collision, Trigger, ConstraintBreak,
+/// WakeSleep and Advance Callbacks, and a ContactModify callback that can be left out
+/// when the scene does not modify contacts.  Due to the number of generic type parameters,
 /// creating a type alias is recommended.
 #[repr(transparent)]
 #[allow(clippy::type_complexity)]
-pub struct PxScene<U, L, S, D, C, OC, OT, OCB, OWS, OA>
+pub struct PxScene<U, L, S, D, C, OC, OT, OCB, OWS, OA, OCM = NoContactModify>
 where
     L: ArticulationLink,
     S: RigidStatic,
     D: RigidDynamic,
     C: ArticulationReducedCoordinate,
-    OC: CollisionCallback,
+    OC: CollisionCallback<L, S, D>,
     OT: TriggerCallback,
     OCB: ConstraintBreakCallback,
     OWS: WakeSleepCallback<L, S, D>,
     OA: AdvanceCallback<L, D>,
+    OCM: ContactModifyCallback<L, S, D>,
 {
     pub(crate) obj: physx_sys::PxScene,
-    phantom_user_data: PhantomData<(U, L, S, D, C, OC, OT, OCB, OWS, OA)>,
+    phantom_user_data: PhantomData<(U, L, S, D, C, OC, OT, OCB, OWS, OA, OCM)>,
 }
 
-unsafe impl<U, L, S, D, C, OC, OT, OCB, OWS, OA> UserData
-    for PxScene<U, L, S, D, C, OC, OT, OCB, OWS, OA>
+unsafe impl<U, L, S, D, C, OC, OT, OCB, OWS, OA, OCM> UserData
+    for PxScene<U, L, S, D, C, OC, OT, OCB, OWS, OA, OCM>
 where
     L: ArticulationLink,
     S: RigidStatic,
     D: RigidDynamic,
     C: ArticulationReducedCoordinate,
-    OC: CollisionCallback,
+    OC: CollisionCallback<L, S, D>,
     OT: TriggerCallback,
     OCB: ConstraintBreakCallback,
     OWS: WakeSleepCallback<L, S, D>,
     OA: AdvanceCallback<L, D>,
+    OCM: ContactModifyCallback<L, S, D>,
 {
     type UserData = U;
 
@@ -190,17 +208,19 @@ where
     }
 }
 
-impl<U, L, S, D, C, OC, OT, OCB, OWS, OA> Drop for PxScene<U, L, S, D, C, OC, OT, OCB, OWS, OA>
+impl<U, L, S, D, C, OC, OT, OCB, OWS, OA, OCM> Drop
+    for PxScene<U, L, S, D, C, OC, OT, OCB, OWS, OA, OCM>
 where
     L: ArticulationLink,
     S: RigidStatic,
     D: RigidDynamic,
     C: ArticulationReducedCoordinate,
-    OC: CollisionCallback,
+    OC: CollisionCallback<L, S, D>,
     OT: TriggerCallback,
     OCB: ConstraintBreakCallback,
     OWS: WakeSleepCallback<L, S, D>,
     OA: AdvanceCallback<L, D>,
+    OCM: ContactModifyCallback<L, S, D>,
 {
     fn drop(&mut self) {
         unsafe {
@@ -208,7 +228,12 @@ where
                 drop_in_place(ptr as *mut _)
             }
             for ptr in self.get_constraints() {
-                drop_in_place(ptr as *mut _)
+                // Joints own their constraint, and are owned by the user rather than the scene
+                let mut type_id = 0;
+                PxConstraint_getExternalReference_mut(ptr.as_mut_ptr(), &mut type_id);
+                if type_id != PxConstraintExtIDs::Joint as u32 {
+                    drop_in_place(ptr as *mut _)
+                }
             }
             for ptr in self.get_articulations() {
                 drop_in_place(ptr as *mut _);
@@ -223,24 +248,26 @@ where
             drop_in_place(self.get_user_data_mut() as *mut _);
             drop_in_place(PxScene_getSimulationEventCallback(self.as_ptr())
                 as *mut PxSimulationEventCallback<L, S, D, OC, OT, OCB, OWS, OA>);
+            OCM::drop_raw_callback(PxScene_getContactModifyCallback(self.as_ptr()));
             PxScene_release_mut(self.as_mut_ptr());
         }
     }
 }
 
-unsafe impl<P, U, L, S, D, C, OC, OT, OCB, OWS, OA> Class<P>
-    for PxScene<U, L, S, D, C, OC, OT, OCB, OWS, OA>
+unsafe impl<P, U, L, S, D, C, OC, OT, OCB, OWS, OA, OCM> Class<P>
+    for PxScene<U, L, S, D, C, OC, OT, OCB, OWS, OA, OCM>
 where
     physx_sys::PxScene: Class<P>,
     L: ArticulationLink,
     S: RigidStatic,
     D: RigidDynamic,
     C: ArticulationReducedCoordinate,
-    OC: CollisionCallback,
+    OC: CollisionCallback<L, S, D>,
     OT: TriggerCallback,
     OCB: ConstraintBreakCallback,
     OWS: WakeSleepCallback<L, S, D>,
     OA: AdvanceCallback<L, D>,
+    OCM: ContactModifyCallback<L, S, D>,
 {
     fn as_ptr(&self) -> *const P {
         self.obj.as_ptr()
@@ -251,47 +278,51 @@ where
     }
 }
 
-unsafe impl<U, L, S, D, C, OC, OT, OCB, OWS, OA> Send
-    for PxScene<U, L, S, D, C, OC, OT, OCB, OWS, OA>
+unsafe impl<U, L, S, D, C, OC, OT, OCB, OWS, OA, OCM> Send
+    for PxScene<U, L, S, D, C, OC, OT, OCB, OWS, OA, OCM>
 where
     L: ArticulationLink + Send,
     S: RigidStatic + Send,
     D: RigidDynamic + Send,
     C: ArticulationReducedCoordinate + Send,
-    OC: CollisionCallback + Send,
+    OC: CollisionCallback<L, S, D> + Send,
     OT: TriggerCallback + Send,
     OCB: ConstraintBreakCallback + Send,
     OWS: WakeSleepCallback<L, S, D>,
     OA: AdvanceCallback<L, D>,
+    OCM: ContactModifyCallback<L, S, D> + Send,
 {
 }
 
-unsafe impl<U, L, S, D, C, OC, OT, OCB, OWS, OA> Sync
-    for PxScene<U, L, S, D, C, OC, OT, OCB, OWS, OA>
+unsafe impl<U, L, S, D, C, OC, OT, OCB, OWS, OA, OCM> Sync
+    for PxScene<U, L, S, D, C, OC, OT, OCB, OWS, OA, OCM>
 where
     L: ArticulationLink + Sync,
     S: RigidStatic + Sync,
     D: RigidDynamic + Sync,
     C: ArticulationReducedCoordinate + Sync,
-    OC: CollisionCallback + Sync,
+    OC: CollisionCallback<L, S, D> + Sync,
     OT: TriggerCallback + Sync,
     OCB: ConstraintBreakCallback + Sync,
     OWS: WakeSleepCallback<L, S, D>,
     OA: AdvanceCallback<L, D>,
+    OCM: ContactModifyCallback<L, S, D>,
 {
 }
 
-impl<U, L, S, D, C, OC, OT, OCB, OWS, OA> Scene for PxScene<U, L, S, D, C, OC, OT, OCB, OWS, OA>
+impl<U, L, S, D, C, OC, OT, OCB, OWS, OA, OCM> Scene
+    for PxScene<U, L, S, D, C, OC, OT, OCB, OWS, OA, OCM>
 where
     L: ArticulationLink,
     S: RigidStatic,
     D: RigidDynamic,
     C: ArticulationReducedCoordinate,
-    OC: CollisionCallback,
+    OC: CollisionCallback<L, S, D>,
     OT: TriggerCallback,
     OCB: ConstraintBreakCallback,
     OWS: WakeSleepCallback<L, S, D>,
     OA: AdvanceCallback<L, D>,
+    OCM: ContactModifyCallback<L, S, D>,
 {
     type ArticulationLink = L;
     type RigidStatic = S;
@@ -337,8 +368,9 @@ pub trait Scene: Class<physx_sys::PxScene> + UserData {
         }
     }
 
-    /// Create a controller manager.
-    fn create_controller_manager<C: Controller>(
+    /// Create a controller manager.  The controllers report the actors of the scene as
+    /// [`Self::ActorMap`].
+    fn create_controller_manager<C: Controller<ActorMap = Self::ActorMap>>(
         &mut self,
         locking_enabled: bool,
     ) -> Option<Owner<PxControllerManager<C>>> {
@@ -477,6 +509,17 @@ pub trait Scene: Class<physx_sys::PxScene> + UserData {
         }
     }
 
+    /// Add the actors, articulations and aggregates of a deserialized collection to the scene.
+    /// Returns false if the collection could not be added.
+    ///
+    /// # Safety
+    /// User data is not serialized, so the objects in the collection have no valid user data.
+    /// The scene drops the user data of its actors, articulations and aggregates, so this is only
+    /// sound if their user data types are zero sized.  The objects must not already be in a scene.
+    unsafe fn add_collection(&mut self, collection: &Collection) -> bool {
+        unsafe { PxScene_addCollection_mut(self.as_mut_ptr(), collection.as_ptr()) }
+    }
+
     /// Add a rpuning structure to the scene.
     fn add_pruning_structure(&mut self, pruning_structure: Owner<PruningStructure>) {
         unsafe {
@@ -659,7 +702,8 @@ pub trait Scene: Class<physx_sys::PxScene> + UserData {
     // Callbacks
 
     /// # Safety
-    /// PxContactModifyCallback does not have a safe wrapper, using it requires use of [`physx_sys`].
+    /// This is the raw interface, prefer [`SceneDescriptor::on_contact_modify`](crate::traits::descriptor::SceneDescriptor::on_contact_modify).
+    /// Replacing a callback set through the descriptor leaks it.
     unsafe fn set_contact_modify_callback(&mut self, callback: &mut PxContactModifyCallback) {
         unsafe {
             PxScene_setContactModifyCallback_mut(self.as_mut_ptr(), callback);
@@ -667,13 +711,13 @@ pub trait Scene: Class<physx_sys::PxScene> + UserData {
     }
 
     /// # Safety
-    /// PxContactModifyCallback does not have a safe wrapper, using it requires use of [`physx_sys`].
+    /// This is the raw interface, prefer [`SceneDescriptor::on_contact_modify`](crate::traits::descriptor::SceneDescriptor::on_contact_modify).
     unsafe fn get_contact_modify_callback(&self) -> &PxContactModifyCallback {
         unsafe { &*PxScene_getContactModifyCallback(self.as_ptr()) }
     }
 
     /// # Safety
-    /// PxCCDContactModifyCallback does not have a safe wrapper, using it requires use of [`physx_sys`].
+    /// This is the raw interface, prefer [`SceneDescriptor::on_contact_modify`](crate::traits::descriptor::SceneDescriptor::on_contact_modify).
     unsafe fn set_ccd_contact_modify_callback(
         &mut self,
         callback: &mut PxCCDContactModifyCallback,
@@ -684,7 +728,7 @@ pub trait Scene: Class<physx_sys::PxScene> + UserData {
     }
 
     /// # Safety
-    /// PxCCDContactModifyCallback does not have a safe wrapper, using it requires use of [`physx_sys`].
+    /// This is the raw interface, prefer [`SceneDescriptor::on_contact_modify`](crate::traits::descriptor::SceneDescriptor::on_contact_modify).
     unsafe fn get_ccd_contact_callback(&self) -> &PxCCDContactModifyCallback {
         unsafe { &*PxScene_getCCDContactModifyCallback(self.as_ptr()) }
     }
@@ -737,6 +781,131 @@ pub trait Scene: Class<physx_sys::PxScene> + UserData {
         unsafe { PxScene_getStaticKinematicFilteringMode(self.as_ptr()) }
     }
 
+    //////////////////////////////////////////////////////////////////////////
+    // Scene Queries
+
+    /// Cast a ray against the shapes in the scene.  Returns at most one hit unless
+    /// `options.mode` is [`QueryMode::Multiple`](crate::scene_query::QueryMode::Multiple).
+    fn raycast(
+        &self,
+        origin: &PxVec3,
+        unit_dir: &PxVec3,
+        distance: f32,
+        options: &QueryOptions,
+    ) -> Vec<RaycastHit<'_, Self::ActorMap>> {
+        unsafe {
+            scene_query::raycast(
+                self.as_ptr(),
+                origin,
+                unit_dir,
+                distance,
+                options,
+                null_mut(),
+            )
+        }
+    }
+
+    /// Cast a ray against the shapes in the scene, letting `filter` decide how each shape is treated.
+    fn raycast_filtered(
+        &self,
+        origin: &PxVec3,
+        unit_dir: &PxVec3,
+        distance: f32,
+        options: &QueryOptions,
+        filter: &mut impl QueryFilter<Self::ActorMap>,
+    ) -> Vec<RaycastHit<'_, Self::ActorMap>> {
+        let mut callback = FilterCallback::raycast(filter);
+        unsafe {
+            scene_query::raycast(
+                self.as_ptr(),
+                origin,
+                unit_dir,
+                distance,
+                options,
+                callback.as_mut_ptr(),
+            )
+        }
+    }
+
+    /// Sweep a geometry starting at `pose` through the scene.  Returns at most one hit unless
+    /// `options.mode` is [`QueryMode::Multiple`](crate::scene_query::QueryMode::Multiple).
+    fn sweep(
+        &self,
+        geometry: &impl Geometry,
+        pose: &PxTransform,
+        unit_dir: &PxVec3,
+        distance: f32,
+        options: &QueryOptions,
+    ) -> Vec<SweepHit<'_, Self::ActorMap>> {
+        unsafe {
+            scene_query::sweep(
+                self.as_ptr(),
+                geometry,
+                pose,
+                unit_dir,
+                distance,
+                options,
+                null_mut(),
+            )
+        }
+    }
+
+    /// Sweep a geometry through the scene, letting `filter` decide how each shape is treated.
+    fn sweep_filtered(
+        &self,
+        geometry: &impl Geometry,
+        pose: &PxTransform,
+        unit_dir: &PxVec3,
+        distance: f32,
+        options: &QueryOptions,
+        filter: &mut impl QueryFilter<Self::ActorMap>,
+    ) -> Vec<SweepHit<'_, Self::ActorMap>> {
+        let mut callback = FilterCallback::sweep(filter);
+        unsafe {
+            scene_query::sweep(
+                self.as_ptr(),
+                geometry,
+                pose,
+                unit_dir,
+                distance,
+                options,
+                callback.as_mut_ptr(),
+            )
+        }
+    }
+
+    /// Find the shapes in the scene overlapping a geometry at `pose`.  Overlaps have no notion
+    /// of distance, so `QueryMode::Closest` behaves like `QueryMode::Any`.
+    fn overlap(
+        &self,
+        geometry: &impl Geometry,
+        pose: &PxTransform,
+        options: &QueryOptions,
+    ) -> Vec<OverlapHit<'_, Self::ActorMap>> {
+        unsafe { scene_query::overlap(self.as_ptr(), geometry, pose, options, null_mut()) }
+    }
+
+    /// Find the shapes in the scene overlapping a geometry, letting `filter` decide how each
+    /// shape is treated.  Overlaps do not distinguish between touching and blocking hits.
+    fn overlap_filtered(
+        &self,
+        geometry: &impl Geometry,
+        pose: &PxTransform,
+        options: &QueryOptions,
+        filter: &mut impl QueryFilter<Self::ActorMap>,
+    ) -> Vec<OverlapHit<'_, Self::ActorMap>> {
+        let mut callback = FilterCallback::overlap(filter);
+        unsafe {
+            scene_query::overlap(
+                self.as_ptr(),
+                geometry,
+                pose,
+                options,
+                callback.as_mut_ptr(),
+            )
+        }
+    }
+
     //////////////////////////////////////////////////////////////////////////
     // Other simulation settings
 
@@ -746,4 +915,34 @@ pub trait Scene: Class<physx_sys::PxScene> + UserData {
             PxScene_setGravity_mut(self.as_mut_ptr(), &PxVec3::new(x, y, z).into());
         }
     }
+
+    //////////////////////////////////////////////////////////////////////////
+    // Debug visualization
+
+    /// Set a debug visualization parameter.  [`VisualizationParameter::Scale`] must be
+    /// non-zero for anything to be visualized.  Returns false if the value was rejected.
+    fn set_visualization_parameter(&mut self, param: VisualizationParameter, value: f32) -> bool {
+        unsafe { PxScene_setVisualizationParameter_mut(self.as_mut_ptr(), param, value) }
+    }
+
+    /// Get a debug visualization parameter.
+    fn get_visualization_parameter(&self, param: VisualizationParameter) -> f32 {
+        unsafe { PxScene_getVisualizationParameter(self.as_ptr(), param) }
+    }
+
+    /// Only visualize objects inside these bounds.
+    fn set_visualization_culling_box(&mut self, bounds: &PxBounds3) {
+        unsafe { PxScene_setVisualizationCullingBox_mut(self.as_mut_ptr(), bounds.as_ptr()) }
+    }
+
+    /// Get the bounds objects are visualized in.
+    fn get_visualization_culling_box(&self) -> PxBounds3 {
+        unsafe { PxScene_getVisualizationCullingBox(self.as_ptr()).into() }
+    }
+
+    /// Get the debug geometry produced by the last simulation step.  The buffer is
+    /// overwritten by the next call to `fetch_results`.
+    fn get_render_buffer(&mut self) -> &RenderBuffer {
+        unsafe { &*(PxScene_getRenderBuffer_mut(self.as_mut_ptr()) as *const RenderBuffer) }
+    }
 }
@@ -0,0 +1,583 @@
+//! Typed results and options for the scene queries on [`Scene`](crate::scene::Scene).
+
+use crate::{
+    geometry::Geometry,
+    math::{PxTransform, PxVec3},
+    rigid_actor::RigidActor,
+    scene::HitFlags,
+    shape::CollisionLayers,
+    traits::Class,
+};
+
+use std::ptr::null;
+
+#[rustfmt::skip]
+use physx_sys::{
+    create_query_filter_callback,
+    PxFilterData,
+    PxOverlapHit,
+    PxQueryFilterCallback,
+    PxQueryFilterCallback_delete,
+    PxQueryFilterData,
+    PxQueryFilterData_new_1,
+    PxQueryHit,
+    PxRaycastHit,
+    PxRigidActor,
+    PxScene,
+    PxSceneQueryExt_overlapAny,
+    PxSceneQueryExt_overlapMultiple,
+    PxSceneQueryExt_raycastMultiple,
+    PxSceneQueryExt_raycastSingle,
+    PxSceneQueryExt_sweepMultiple,
+    PxSceneQueryExt_sweepSingle,
+    PxShape,
+    PxSweepHit,
+    QueryPostFilterCallback,
+};
+
+#[rustfmt::skip]
+pub use physx_sys::{
+    PxQueryFlag as QueryFlag,
+    PxQueryFlags as QueryFlags,
+    PxQueryHitType as QueryHitType,
+};
+
+/// How many hits a scene query should look for.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum QueryMode {
+    /// Report the closest blocking hit.
+    Closest,
+    /// Report any blocking hit, not necessarily the closest.  Stops traversal at the first hit found.
+    Any,
+    /// Report up to `max_hits` hits.  Touching hits are not ordered, and any hits past
+    /// `max_hits` are dropped.  Without a [`QueryFilter`] every hit is reported as touching,
+    /// with one the query stops at the closest blocking hit, which is placed last.
+    Multiple { max_hits: u32 },
+}
+
+/// Options shared by raycasts, sweeps and overlaps.
+#[derive(Copy, Clone, Debug)]
+pub struct QueryOptions {
+    /// How many hits to look for.
+    pub mode: QueryMode,
+    /// Which fields of the hits should be computed.  Ignored by overlaps.
+    pub hit_flags: HitFlags,
+    /// Which actors to traverse, and how filtering should be done.
+    pub query_flags: QueryFlags,
+    /// Only shapes whose query filter data (see [`Shape::set_query_filter_data`](crate::shape::Shape::set_query_filter_data))
+    /// shares a layer with this will be hit.  Empty means no filtering.
+    pub layers: CollisionLayers,
+    /// Inflation of the swept geometry.  Only used by sweeps.
+    pub inflation: f32,
+}
+
+impl Default for QueryOptions {
+    fn default() -> Self {
+        Self {
+            mode: QueryMode::Closest,
+            hit_flags: HitFlags::Default,
+            query_flags: QueryFlags::Static | QueryFlags::Dynamic,
+            layers: CollisionLayers::empty(),
+            inflation: 0.0,
+        }
+    }
+}
+
+impl QueryOptions {
+    /// Options for a query in the given mode, all other fields are defaulted.
+    pub fn new(mode: QueryMode) -> Self {
+        Self {
+            mode,
+            ..Default::default()
+        }
+    }
+
+    /// Build the `PxQueryFilterData` to pass to PhysX.
+    pub(crate) fn filter_data(&self, has_filter: bool) -> PxQueryFilterData {
+        let mut flags = self.query_flags;
+        if has_filter && !flags.intersects(QueryFlags::Prefilter | QueryFlags::Postfilter) {
+            flags |= QueryFlags::Prefilter;
+        }
+        match self.mode {
+            QueryMode::Any => flags |= QueryFlags::AnyHit,
+            QueryMode::Multiple { .. } if !has_filter => flags |= QueryFlags::NoBlock,
+            _ => (),
+        }
+        let data = PxFilterData {
+            word0: self.layers.bits(),
+            word1: 0,
+            word2: 0,
+            word3: 0,
+        };
+        unsafe { PxQueryFilterData_new_1(&data, flags) }
+    }
+}
+
+/// A hit reported by a raycast.  Parametrized by the actor type of the scene.
+pub struct RaycastHit<'a, A: RigidActor> {
+    /// The actor that was hit.
+    pub actor: &'a A,
+    /// The shape of the actor that was hit.
+    pub shape: &'a A::Shape,
+    /// Triangle index for mesh and height field hits, only valid if `HitFlag::FaceIndex` is set.
+    pub face_index: u32,
+    /// Which fields of this hit are valid.
+    pub flags: HitFlags,
+    /// World space position of the hit, only valid if `HitFlag::Position` is set.
+    pub position: PxVec3,
+    /// World space normal of the hit, only valid if `HitFlag::Normal` is set.
+    pub normal: PxVec3,
+    /// Distance from the ray origin to the hit.
+    pub distance: f32,
+    /// Barycentric u coordinate of the hit, only valid if `HitFlag::Uv` is set.
+    pub u: f32,
+    /// Barycentric v coordinate of the hit, only valid if `HitFlag::Uv` is set.
+    pub v: f32,
+}
+
+impl<'a, A: RigidActor> RaycastHit<'a, A> {
+    /// # Safety
+    /// The actor and shape pointers of `hit` must be valid, and point to an `A` and `A::Shape`.
+    pub(crate) unsafe fn from_raw(hit: &PxRaycastHit) -> Self {
+        unsafe {
+            Self {
+                actor: &*(hit.actor as *const A),
+                shape: &*(hit.shape as *const A::Shape),
+                face_index: hit.faceIndex,
+                flags: hit.flags,
+                position: hit.position.into(),
+                normal: hit.normal.into(),
+                distance: hit.distance,
+                u: hit.u,
+                v: hit.v,
+            }
+        }
+    }
+}
+
+/// A hit reported by a sweep.  Parametrized by the actor type of the scene.
+pub struct SweepHit<'a, A: RigidActor> {
+    /// The actor that was hit.
+    pub actor: &'a A,
+    /// The shape of the actor that was hit.
+    pub shape: &'a A::Shape,
+    /// Triangle index for mesh and height field hits, only valid if `HitFlag::FaceIndex` is set.
+    pub face_index: u32,
+    /// Which fields of this hit are valid.
+    pub flags: HitFlags,
+    /// World space position of the hit, only valid if `HitFlag::Position` is set.
+    pub position: PxVec3,
+    /// World space normal of the hit, only valid if `HitFlag::Normal` is set.
+    pub normal: PxVec3,
+    /// Distance the geometry travelled before the hit.
+    pub distance: f32,
+}
+
+impl<'a, A: RigidActor> SweepHit<'a, A> {
+    /// # Safety
+    /// The actor and shape pointers of `hit` must be valid, and point to an `A` and `A::Shape`.
+    pub(crate) unsafe fn from_raw(hit: &PxSweepHit) -> Self {
+        unsafe {
+            Self {
+                actor: &*(hit.actor as *const A),
+                shape: &*(hit.shape as *const A::Shape),
+                face_index: hit.faceIndex,
+                flags: hit.flags,
+                position: hit.position.into(),
+                normal: hit.normal.into(),
+                distance: hit.distance,
+            }
+        }
+    }
+}
+
+/// A hit reported by an overlap.  Parametrized by the actor type of the scene.
+pub struct OverlapHit<'a, A: RigidActor> {
+    /// The actor that was hit.
+    pub actor: &'a A,
+    /// The shape of the actor that was hit.
+    pub shape: &'a A::Shape,
+    /// Triangle index for mesh and height field hits.
+    pub face_index: u32,
+}
+
+impl<'a, A: RigidActor> OverlapHit<'a, A> {
+    /// # Safety
+    /// The actor and shape pointers of `hit` must be valid, and point to an `A` and `A::Shape`.
+    pub(crate) unsafe fn from_raw(hit: &PxOverlapHit) -> Self {
+        unsafe {
+            Self {
+                actor: &*(hit.actor as *const A),
+                shape: &*(hit.shape as *const A::Shape),
+                face_index: hit.faceIndex,
+            }
+        }
+    }
+}
+
+/// A hit passed to [`QueryFilter::post_filter`], the variant depends on the kind of query.
+pub enum PostFilterHit<'a, A: RigidActor> {
+    Raycast(RaycastHit<'a, A>),
+    Sweep(SweepHit<'a, A>),
+    /// The face index of overlap hits is not valid during filtering.
+    Overlap(OverlapHit<'a, A>),
+}
+
+/// User filtering of scene queries, see [`Scene::raycast_filtered`](crate::scene::Scene::raycast_filtered).
+///
+/// The pre-filter runs before the exact intersection test when `QueryFlag::Prefilter` is set,
+/// and the post-filter after it when `QueryFlag::Postfilter` is set.  If neither flag is set in
+/// the query options, only the pre-filter is run.  Returning `QueryHitType::None` drops the shape,
+/// `Touch` reports it without stopping the query, and `Block` reports it and shortens the query
+/// to this hit.
+///
+/// Closures taking the filter data, actor and shape are query filters that only pre-filter.
+pub trait QueryFilter<A: RigidActor> {
+    /// Filter a shape before the exact intersection test.  The modifiable subset of `hit_flags`
+    /// can be changed for this shape.
+    #[allow(unused_variables)]
+    fn pre_filter(
+        &mut self,
+        filter_data: &PxFilterData,
+        actor: &A,
+        shape: &A::Shape,
+        hit_flags: &mut HitFlags,
+    ) -> QueryHitType {
+        QueryHitType::Block
+    }
+
+    /// Filter a hit after the exact intersection test.
+    #[allow(unused_variables)]
+    fn post_filter(
+        &mut self,
+        filter_data: &PxFilterData,
+        hit: PostFilterHit<'_, A>,
+    ) -> QueryHitType {
+        QueryHitType::Block
+    }
+}
+
+impl<A, F> QueryFilter<A> for F
+where
+    A: RigidActor,
+    F: FnMut(&PxFilterData, &A, &A::Shape) -> QueryHitType,
+{
+    fn pre_filter(
+        &mut self,
+        filter_data: &PxFilterData,
+        actor: &A,
+        shape: &A::Shape,
+        _hit_flags: &mut HitFlags,
+    ) -> QueryHitType {
+        self(filter_data, actor, shape)
+    }
+}
+
+unsafe extern "C" fn pre_filter_shim<A: RigidActor, F: QueryFilter<A>>(
+    filter_data: *const PxFilterData,
+    shape: *const PxShape,
+    actor: *const PxRigidActor,
+    hit_flags: *mut HitFlags,
+    user_data: *mut std::ffi::c_void,
+) -> QueryHitType {
+    unsafe {
+        (*(user_data as *mut F)).pre_filter(
+            &*filter_data,
+            &*(actor as *const A),
+            &*(shape as *const A::Shape),
+            &mut *hit_flags,
+        )
+    }
+}
+
+unsafe extern "C" fn raycast_post_filter_shim<A: RigidActor, F: QueryFilter<A>>(
+    filter_data: *const PxFilterData,
+    hit: *const PxQueryHit,
+    _shape: *const PxShape,
+    _actor: *const PxRigidActor,
+    user_data: *mut std::ffi::c_void,
+) -> QueryHitType {
+    unsafe {
+        let hit = RaycastHit::from_raw(&*(hit as *const PxRaycastHit));
+        (*(user_data as *mut F)).post_filter(&*filter_data, PostFilterHit::Raycast(hit))
+    }
+}
+
+unsafe extern "C" fn sweep_post_filter_shim<A: RigidActor, F: QueryFilter<A>>(
+    filter_data: *const PxFilterData,
+    hit: *const PxQueryHit,
+    _shape: *const PxShape,
+    _actor: *const PxRigidActor,
+    user_data: *mut std::ffi::c_void,
+) -> QueryHitType {
+    unsafe {
+        let hit = SweepHit::from_raw(&*(hit as *const PxSweepHit));
+        (*(user_data as *mut F)).post_filter(&*filter_data, PostFilterHit::Sweep(hit))
+    }
+}
+
+unsafe extern "C" fn overlap_post_filter_shim<A: RigidActor, F: QueryFilter<A>>(
+    filter_data: *const PxFilterData,
+    hit: *const PxQueryHit,
+    _shape: *const PxShape,
+    _actor: *const PxRigidActor,
+    user_data: *mut std::ffi::c_void,
+) -> QueryHitType {
+    unsafe {
+        let hit = OverlapHit::from_raw(&*(hit as *const PxOverlapHit));
+        (*(user_data as *mut F)).post_filter(&*filter_data, PostFilterHit::Overlap(hit))
+    }
+}
+
+/// Owns the PxQueryFilterCallback forwarding to a [`QueryFilter`] for the duration of a query.
+pub(crate) struct FilterCallback(*mut PxQueryFilterCallback);
+
+impl FilterCallback {
+    fn new<A: RigidActor, F: QueryFilter<A>>(
+        filter: &mut F,
+        post_filter: QueryPostFilterCallback,
+    ) -> Self {
+        Self(unsafe {
+            create_query_filter_callback(
+                pre_filter_shim::<A, F>,
+                post_filter,
+                filter as *mut F as *mut std::ffi::c_void,
+            )
+        })
+    }
+
+    pub(crate) fn raycast<A: RigidActor, F: QueryFilter<A>>(filter: &mut F) -> Self {
+        Self::new::<A, F>(filter, raycast_post_filter_shim::<A, F>)
+    }
+
+    pub(crate) fn sweep<A: RigidActor, F: QueryFilter<A>>(filter: &mut F) -> Self {
+        Self::new::<A, F>(filter, sweep_post_filter_shim::<A, F>)
+    }
+
+    pub(crate) fn overlap<A: RigidActor, F: QueryFilter<A>>(filter: &mut F) -> Self {
+        Self::new::<A, F>(filter, overlap_post_filter_shim::<A, F>)
+    }
+
+    pub(crate) fn as_mut_ptr(&mut self) -> *mut PxQueryFilterCallback {
+        self.0
+    }
+}
+
+impl Drop for FilterCallback {
+    fn drop(&mut self) {
+        unsafe { PxQueryFilterCallback_delete(self.0) }
+    }
+}
+
+/// Zeroed raw hits, used to fill hit buffers before handing them to PhysX.
+pub(crate) trait EmptyHit: Sized {
+    fn empty() -> Self {
+        // Safety: all the raw hit types are plain old data, and null pointers are valid for them.
+        unsafe { std::mem::zeroed() }
+    }
+}
+
+impl EmptyHit for PxRaycastHit {}
+impl EmptyHit for PxSweepHit {}
+impl EmptyHit for PxOverlapHit {}
+
+/// Convert the result of a `PxSceneQueryExt_*Multiple` call into the number of valid hits in the buffer.
+fn multiple_hit_count(result: i32, buffer_len: usize) -> usize {
+    // -1 means the buffer overflowed, in which case it is full
+    if result < 0 {
+        buffer_len
+    } else {
+        result as usize
+    }
+}
+
+/// Shared implementation of [`Scene::raycast`](crate::scene::Scene::raycast) and
+/// [`Scene::raycast_filtered`](crate::scene::Scene::raycast_filtered).
+///
+/// # Safety
+/// The actors in `scene` must all be `A`s, and `filter`, if not null, must stay valid for the call.
+pub(crate) unsafe fn raycast<'a, A: RigidActor>(
+    scene: *const PxScene,
+    origin: &PxVec3,
+    unit_dir: &PxVec3,
+    distance: f32,
+    options: &QueryOptions,
+    filter: *mut PxQueryFilterCallback,
+) -> Vec<RaycastHit<'a, A>> {
+    let filter_data = options.filter_data(!filter.is_null());
+    match options.mode {
+        QueryMode::Closest | QueryMode::Any => {
+            let mut hit = PxRaycastHit::empty();
+            let found = unsafe {
+                PxSceneQueryExt_raycastSingle(
+                    scene,
+                    origin.as_ptr(),
+                    unit_dir.as_ptr(),
+                    distance,
+                    options.hit_flags,
+                    &mut hit,
+                    &filter_data,
+                    filter,
+                    null(),
+                )
+            };
+            if found {
+                vec![unsafe { RaycastHit::from_raw(&hit) }]
+            } else {
+                Vec::new()
+            }
+        }
+        QueryMode::Multiple { max_hits } => {
+            if max_hits == 0 {
+                return Vec::new();
+            }
+            let mut buffer = vec![PxRaycastHit::empty(); max_hits as usize];
+            let mut blocking_hit = false;
+            let result = unsafe {
+                PxSceneQueryExt_raycastMultiple(
+                    scene,
+                    origin.as_ptr(),
+                    unit_dir.as_ptr(),
+                    distance,
+                    options.hit_flags,
+                    buffer.as_mut_ptr(),
+                    max_hits,
+                    &mut blocking_hit,
+                    &filter_data,
+                    filter,
+                    null(),
+                )
+            };
+            buffer[..multiple_hit_count(result, buffer.len())]
+                .iter()
+                .map(|hit| unsafe { RaycastHit::from_raw(hit) })
+                .collect()
+        }
+    }
+}
+
+/// Shared implementation of [`Scene::sweep`](crate::scene::Scene::sweep) and
+/// [`Scene::sweep_filtered`](crate::scene::Scene::sweep_filtered).
+///
+/// # Safety
+/// The actors in `scene` must all be `A`s, and `filter`, if not null, must stay valid for the call.
+pub(crate) unsafe fn sweep<'a, A: RigidActor>(
+    scene: *const PxScene,
+    geometry: &impl Geometry,
+    pose: &PxTransform,
+    unit_dir: &PxVec3,
+    distance: f32,
+    options: &QueryOptions,
+    filter: *mut PxQueryFilterCallback,
+) -> Vec<SweepHit<'a, A>> {
+    let filter_data = options.filter_data(!filter.is_null());
+    match options.mode {
+        QueryMode::Closest | QueryMode::Any => {
+            let mut hit = PxSweepHit::empty();
+            let found = unsafe {
+                PxSceneQueryExt_sweepSingle(
+                    scene,
+                    geometry.as_ptr(),
+                    pose.as_ptr(),
+                    unit_dir.as_ptr(),
+                    distance,
+                    options.hit_flags,
+                    &mut hit,
+                    &filter_data,
+                    filter,
+                    null(),
+                    options.inflation,
+                )
+            };
+            if found {
+                vec![unsafe { SweepHit::from_raw(&hit) }]
+            } else {
+                Vec::new()
+            }
+        }
+        QueryMode::Multiple { max_hits } => {
+            if max_hits == 0 {
+                return Vec::new();
+            }
+            let mut buffer = vec![PxSweepHit::empty(); max_hits as usize];
+            let mut blocking_hit = false;
+            let result = unsafe {
+                PxSceneQueryExt_sweepMultiple(
+                    scene,
+                    geometry.as_ptr(),
+                    pose.as_ptr(),
+                    unit_dir.as_ptr(),
+                    distance,
+                    options.hit_flags,
+                    buffer.as_mut_ptr(),
+                    max_hits,
+                    &mut blocking_hit,
+                    &filter_data,
+                    filter,
+                    null(),
+                    options.inflation,
+                )
+            };
+            buffer[..multiple_hit_count(result, buffer.len())]
+                .iter()
+                .map(|hit| unsafe { SweepHit::from_raw(hit) })
+                .collect()
+        }
+    }
+}
+
+/// Shared implementation of [`Scene::overlap`](crate::scene::Scene::overlap) and
+/// [`Scene::overlap_filtered`](crate::scene::Scene::overlap_filtered).
+///
+/// # Safety
+/// The actors in `scene` must all be `A`s, and `filter`, if not null, must stay valid for the call.
+pub(crate) unsafe fn overlap<'a, A: RigidActor>(
+    scene: *const PxScene,
+    geometry: &impl Geometry,
+    pose: &PxTransform,
+    options: &QueryOptions,
+    filter: *mut PxQueryFilterCallback,
+) -> Vec<OverlapHit<'a, A>> {
+    let filter_data = options.filter_data(!filter.is_null());
+    match options.mode {
+        QueryMode::Closest | QueryMode::Any => {
+            let mut hit = PxOverlapHit::empty();
+            let found = unsafe {
+                PxSceneQueryExt_overlapAny(
+                    scene,
+                    geometry.as_ptr(),
+                    pose.as_ptr(),
+                    &mut hit,
+                    &filter_data,
+                    filter,
+                )
+            };
+            if found {
+                vec![unsafe { OverlapHit::from_raw(&hit) }]
+            } else {
+                Vec::new()
+            }
+        }
+        QueryMode::Multiple { max_hits } => {
+            if max_hits == 0 {
+                return Vec::new();
+            }
+            let mut buffer = vec![PxOverlapHit::empty(); max_hits as usize];
+            let result = unsafe {
+                PxSceneQueryExt_overlapMultiple(
+                    scene,
+                    geometry.as_ptr(),
+                    pose.as_ptr(),
+                    buffer.as_mut_ptr(),
+                    max_hits,
+                    &filter_data,
+                    filter,
+                )
+            };
+            buffer[..multiple_hit_count(result, buffer.len())]
+                .iter()
+                .map(|hit| unsafe { OverlapHit::from_raw(hit) })
+                .collect()
+        }
+    }
+}
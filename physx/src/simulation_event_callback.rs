@@ -1,14 +1,16 @@
-use std::{ffi::c_void, marker::PhantomData, ptr::null_mut, slice};
+use std::{ffi::c_void, marker::PhantomData, ops::Deref, ptr::null_mut, slice};
 
 #[rustfmt::skip]
 use crate::{
     actor::ActorMap,
     articulation_link::ArticulationLink,
-    math::PxTransform,
+    math::{PxTransform, PxVec3},
     owner::Owner,
+    rigid_actor::RigidActor,
     rigid_body::RigidBodyMap,
     rigid_dynamic::RigidDynamic,
     rigid_static::RigidStatic,
+    shape::Shape,
     traits::Class,
 };
 
@@ -20,6 +22,19 @@ use physx_sys::{
     PxConstraintInfo,
     PxContactPair,
     PxContactPairHeader,
+    PxContactPair_getInternalFaceIndices,
+    PxContactStreamIterator,
+    PxContactStreamIterator_getContactNormal,
+    PxContactStreamIterator_getContactPoint,
+    PxContactStreamIterator_getFaceIndex0,
+    PxContactStreamIterator_getFaceIndex1,
+    PxContactStreamIterator_getSeparation,
+    PxContactStreamIterator_hasNextContact,
+    PxContactStreamIterator_hasNextPatch,
+    PxContactStreamIterator_new,
+    PxContactStreamIterator_nextContact_mut,
+    PxContactStreamIterator_nextPatch_mut,
+    PxShape_getMaterialFromInternalFaceIndex,
     PxTriggerPair,
     SimulationEventCallbackInfo,
 };
@@ -34,7 +49,7 @@ where
     L: ArticulationLink,
     S: RigidStatic,
     D: RigidDynamic,
-    OC: CollisionCallback,
+    OC: CollisionCallback<L, S, D>,
     OT: TriggerCallback,
     OCB: ConstraintBreakCallback,
     OWS: WakeSleepCallback<L, S, D>,
@@ -50,7 +65,7 @@ where
     L: ArticulationLink,
     S: RigidStatic,
     D: RigidDynamic,
-    OC: CollisionCallback,
+    OC: CollisionCallback<L, S, D>,
     OT: TriggerCallback,
     OCB: ConstraintBreakCallback,
     OWS: WakeSleepCallback<L, S, D>,
@@ -70,7 +85,7 @@ where
     L: ArticulationLink,
     S: RigidStatic,
     D: RigidDynamic,
-    OC: CollisionCallback,
+    OC: CollisionCallback<L, S, D>,
     OT: TriggerCallback,
     OCB: ConstraintBreakCallback,
     OWS: WakeSleepCallback<L, S, D>,
@@ -89,7 +104,7 @@ where
         L: ArticulationLink,
         S: RigidStatic,
         D: RigidDynamic,
-        OC: CollisionCallback,
+        OC: CollisionCallback<L, S, D>,
         OT: TriggerCallback,
         OCB: ConstraintBreakCallback,
         OWS: WakeSleepCallback<L, S, D>,
@@ -132,7 +147,7 @@ where
     S: RigidStatic,
     D: RigidDynamic,
 
-    OC: CollisionCallback,
+    OC: CollisionCallback<L, S, D>,
     OT: TriggerCallback,
     OCB: ConstraintBreakCallback,
     OWS: WakeSleepCallback<L, S, D>,
@@ -163,14 +178,37 @@ where
     }
 }
 
-/// A trait for onCollision()
-pub trait CollisionCallback: Sized {
-    fn on_collision(&mut self, header: &PxContactPairHeader, pairs: &[PxContactPair]);
+#[rustfmt::skip]
+pub use physx_sys::{
+    PxContactPairFlag as ContactPairFlag,
+    PxContactPairFlags as ContactPairFlags,
+    PxContactPairHeaderFlag as ContactPairHeaderFlag,
+    PxContactPairHeaderFlags as ContactPairHeaderFlags,
+    PxPairFlag as PairFlag,
+    PxPairFlags as PairFlags,
+};
+
+/// A trait for onCollision().  Parametrized by the [`ArticulationLink`], [`RigidStatic`],
+/// and [`RigidDynamic`] types of the scene it is in.
+pub trait CollisionCallback<L: ArticulationLink, S: RigidStatic, D: RigidDynamic>: Sized {
+    fn on_collision(&mut self, header: &ContactPairHeader<L, S, D>, pairs: &[ContactPair<L, S, D>]);
 }
 
-impl<T> CollisionCallbackRaw for T where T: CollisionCallback {}
+impl<T, L, S, D> CollisionCallbackRaw<L, S, D> for T
+where
+    T: CollisionCallback<L, S, D>,
+    L: ArticulationLink,
+    S: RigidStatic,
+    D: RigidDynamic,
+{
+}
 
-trait CollisionCallbackRaw: CollisionCallback {
+trait CollisionCallbackRaw<L, S, D>: CollisionCallback<L, S, D>
+where
+    L: ArticulationLink,
+    S: RigidStatic,
+    D: RigidDynamic,
+{
     unsafe extern "C" fn callback(
         user_data: *mut c_void,
         header: *const physx_sys::PxContactPairHeader,
@@ -180,8 +218,8 @@ trait CollisionCallbackRaw: CollisionCallback {
         unsafe {
             Self::on_collision(
                 &mut *(user_data as *mut Self),
-                &*header,
-                slice::from_raw_parts(pairs, nb_pairs as usize),
+                &*(header as *const ContactPairHeader<L, S, D>),
+                slice::from_raw_parts(pairs as *const ContactPair<L, S, D>, nb_pairs as usize),
             )
         }
     }
@@ -194,6 +232,247 @@ trait CollisionCallbackRaw: CollisionCallback {
     }
 }
 
+/// A new type wrapper for the PxContactPairHeader passed to [`CollisionCallback`], which resolves
+/// the actors to the types of the scene.  Derefs to the raw header.
+#[repr(transparent)]
+pub struct ContactPairHeader<L, S, D>
+where
+    L: ArticulationLink,
+    S: RigidStatic,
+    D: RigidDynamic,
+{
+    obj: PxContactPairHeader,
+    phantom_user_data: PhantomData<(*const L, *const S, *const D)>,
+}
+
+impl<L, S, D> Deref for ContactPairHeader<L, S, D>
+where
+    L: ArticulationLink,
+    S: RigidStatic,
+    D: RigidDynamic,
+{
+    type Target = PxContactPairHeader;
+
+    fn deref(&self) -> &Self::Target {
+        &self.obj
+    }
+}
+
+impl<L, S, D> ContactPairHeader<L, S, D>
+where
+    L: ArticulationLink,
+    S: RigidStatic,
+    D: RigidDynamic,
+{
+    /// Get the two actors in contact, `None` for an actor that has been deleted.
+    pub fn actors(&self) -> [Option<&ActorMap<L, S, D>>; 2] {
+        let removed = [
+            ContactPairHeaderFlags::RemovedActor0,
+            ContactPairHeaderFlags::RemovedActor1,
+        ];
+        let mut actors = [None, None];
+        for (index, actor) in actors.iter_mut().enumerate() {
+            if !self.obj.flags.contains(removed[index]) {
+                *actor = unsafe { (self.obj.actors[index] as *const ActorMap<L, S, D>).as_ref() };
+            }
+        }
+        actors
+    }
+
+    /// Get the flags of the header.
+    pub fn flags(&self) -> ContactPairHeaderFlags {
+        self.obj.flags
+    }
+}
+
+/// A new type wrapper for the PxContactPair passed to [`CollisionCallback`], which resolves
+/// the shapes to the types of the scene and iterates the contact points.  Derefs to the raw pair.
+#[repr(transparent)]
+pub struct ContactPair<L, S, D>
+where
+    L: ArticulationLink,
+    S: RigidStatic,
+    D: RigidDynamic,
+{
+    obj: PxContactPair,
+    phantom_user_data: PhantomData<(*const L, *const S, *const D)>,
+}
+
+impl<L, S, D> Deref for ContactPair<L, S, D>
+where
+    L: ArticulationLink,
+    S: RigidStatic,
+    D: RigidDynamic,
+{
+    type Target = PxContactPair;
+
+    fn deref(&self) -> &Self::Target {
+        &self.obj
+    }
+}
+
+type ContactShape<L, S, D> = <ActorMap<L, S, D> as RigidActor>::Shape;
+type ContactMaterial<L, S, D> = <ContactShape<L, S, D> as Shape>::Material;
+
+impl<L, S, D> ContactPair<L, S, D>
+where
+    L: ArticulationLink,
+    S: RigidStatic,
+    D: RigidDynamic,
+{
+    /// Get the two shapes in contact, `None` for a shape that has been deleted.
+    pub fn shapes(&self) -> [Option<&ContactShape<L, S, D>>; 2] {
+        let removed = [
+            ContactPairFlags::RemovedShape0,
+            ContactPairFlags::RemovedShape1,
+        ];
+        let mut shapes = [None, None];
+        for (index, shape) in shapes.iter_mut().enumerate() {
+            if !self.obj.flags.contains(removed[index]) {
+                *shape =
+                    unsafe { (self.obj.shapes[index] as *const ContactShape<L, S, D>).as_ref() };
+            }
+        }
+        shapes
+    }
+
+    /// Get the materials of the two shapes at a contact point, resolving per-face materials of
+    /// triangle meshes and height fields.  `None` for a shape that has been deleted.
+    pub fn materials(&self, point: &ContactPoint) -> [Option<&ContactMaterial<L, S, D>>; 2] {
+        let face_indices = [point.face_index0, point.face_index1];
+        let mut materials = [None, None];
+        for (index, shape) in self.shapes().into_iter().enumerate() {
+            if let Some(shape) = shape {
+                materials[index] = unsafe {
+                    (PxShape_getMaterialFromInternalFaceIndex(shape.as_ptr(), face_indices[index])
+                        as *const ContactMaterial<L, S, D>)
+                        .as_ref()
+                };
+            }
+        }
+        materials
+    }
+
+    /// Get the events that caused this pair to be reported.
+    pub fn events(&self) -> PairFlags {
+        self.obj.events
+    }
+
+    /// Get the flags of the pair.
+    pub fn flags(&self) -> ContactPairFlags {
+        self.obj.flags
+    }
+
+    /// Get the number of contact points in the pair.
+    pub fn contact_count(&self) -> usize {
+        self.obj.contactCount as usize
+    }
+
+    /// Iterate the contact points of the pair.  Impulses are only available if
+    /// `PairFlag::NotifyContactPoints` was requested by the filter shader and the pair was solved.
+    pub fn contact_points(&self) -> ContactPoints<'_> {
+        ContactPoints {
+            iter: unsafe {
+                PxContactStreamIterator_new(
+                    self.obj.contactPatches,
+                    self.obj.contactPoints,
+                    PxContactPair_getInternalFaceIndices(&self.obj),
+                    self.obj.patchCount as u32,
+                    self.obj.contactCount as u32,
+                )
+            },
+            impulses: self
+                .obj
+                .flags
+                .contains(ContactPairFlags::InternalHasImpulses)
+                .then_some(self.obj.contactImpulses),
+            flipped: self
+                .obj
+                .flags
+                .contains(ContactPairFlags::InternalContactsAreFlipped),
+            in_patch: false,
+            index: 0,
+            phantom: PhantomData,
+        }
+    }
+}
+
+/// A single contact point between two shapes, in world space.
+#[derive(Copy, Clone)]
+pub struct ContactPoint {
+    pub position: PxVec3,
+    /// The contact normal, pointing from the second shape towards the first.
+    pub normal: PxVec3,
+    /// The separation of the shapes along the normal, negative when they penetrate.
+    pub separation: f32,
+    /// The impulse applied at the contact point, zero if impulses were not reported.
+    pub impulse: PxVec3,
+    /// The internal face index of the first shape, for triangle meshes and height fields.
+    pub face_index0: u32,
+    /// The internal face index of the second shape, for triangle meshes and height fields.
+    pub face_index1: u32,
+}
+
+/// An iterator over the contact points of a [`ContactPair`].
+pub struct ContactPoints<'a> {
+    iter: PxContactStreamIterator,
+    impulses: Option<*const f32>,
+    flipped: bool,
+    in_patch: bool,
+    index: usize,
+    phantom: PhantomData<&'a PxContactPair>,
+}
+
+impl<'a> Iterator for ContactPoints<'a> {
+    type Item = ContactPoint;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        unsafe {
+            loop {
+                if self.in_patch && PxContactStreamIterator_hasNextContact(&self.iter) {
+                    break;
+                }
+                if !PxContactStreamIterator_hasNextPatch(&self.iter) {
+                    return None;
+                }
+                PxContactStreamIterator_nextPatch_mut(&mut self.iter);
+                self.in_patch = true;
+            }
+            PxContactStreamIterator_nextContact_mut(&mut self.iter);
+
+            let normal: PxVec3 = (*PxContactStreamIterator_getContactNormal(&self.iter)).into();
+            let mut face_indices = [
+                PxContactStreamIterator_getFaceIndex0(&self.iter),
+                PxContactStreamIterator_getFaceIndex1(&self.iter),
+            ];
+            if self.flipped {
+                face_indices.swap(0, 1);
+            }
+            let impulse = match self.impulses {
+                Some(impulses) => {
+                    let impulse = *impulses.add(self.index);
+                    PxVec3::new(
+                        normal.x() * impulse,
+                        normal.y() * impulse,
+                        normal.z() * impulse,
+                    )
+                }
+                None => PxVec3::new(0.0, 0.0, 0.0),
+            };
+            self.index += 1;
+
+            Some(ContactPoint {
+                position: (*PxContactStreamIterator_getContactPoint(&self.iter)).into(),
+                normal,
+                separation: PxContactStreamIterator_getSeparation(&self.iter),
+                impulse,
+                face_index0: face_indices[0],
+                face_index1: face_indices[1],
+            })
+        }
+    }
+}
+
 /// A trait for onTrigger().
 pub trait TriggerCallback: Sized {
     fn on_trigger(&mut self, pairs: &[PxTriggerPair]);
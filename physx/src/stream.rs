@@ -0,0 +1,52 @@
+//! Memory backed PhysX streams for cooking and serialization.
+
+use std::slice;
+
+#[rustfmt::skip]
+use physx_sys::{
+    phys_PxGetAllocatorCallback,
+    PxDefaultMemoryInputData_new_alloc,
+    PxDefaultMemoryOutputStream_delete,
+    PxDefaultMemoryOutputStream_getData,
+    PxDefaultMemoryOutputStream_getSize,
+    PxDefaultMemoryOutputStream_new_alloc,
+    PxInputData,
+    PxInputData_delete,
+    PxOutputStream,
+};
+
+/// Run `write` on a memory stream and return what it wrote, or None if it returned false.
+pub(crate) fn write_to_vec(write: impl FnOnce(*mut PxOutputStream) -> bool) -> Option<Vec<u8>> {
+    unsafe {
+        let stream = PxDefaultMemoryOutputStream_new_alloc(phys_PxGetAllocatorCallback());
+        let result = if write(stream as *mut PxOutputStream) {
+            let data = PxDefaultMemoryOutputStream_getData(stream);
+            let size = PxDefaultMemoryOutputStream_getSize(stream) as usize;
+            if size == 0 {
+                Some(Vec::new())
+            } else {
+                Some(slice::from_raw_parts(data, size).to_vec())
+            }
+        } else {
+            None
+        };
+        PxDefaultMemoryOutputStream_delete(stream);
+        result
+    }
+}
+
+/// Run `read` on an input stream over `data`.  The stream does not copy `data`.  Returns
+/// None if `data` is too large for PhysX streams, which are limited to `u32::MAX` bytes.
+pub(crate) fn read_from_slice<T>(
+    data: &[u8],
+    read: impl FnOnce(*mut PxInputData) -> Option<T>,
+) -> Option<T> {
+    let len = u32::try_from(data.len()).ok()?;
+    unsafe {
+        let input =
+            PxDefaultMemoryInputData_new_alloc(data.as_ptr() as *mut u8, len) as *mut PxInputData;
+        let result = read(input);
+        PxInputData_delete(input);
+        result
+    }
+}
@@ -14,11 +14,13 @@ use physx_sys::{
     PxD6Joint,
     PxDistanceJoint,
     PxFixedJoint,
+    PxGearJoint,
     PxHeightField,
     PxJoint,
     PxMaterial,
     PxPrismaticJoint,
     PxPruningStructure,
+    PxRackAndPinionJoint,
     PxRefCounted,
     PxRevoluteJoint,
     PxRigidActor,
@@ -193,7 +195,9 @@ DeriveClass!(PxContactJoint: PxJoint, PxBase);
 DeriveClass!(PxD6Joint: PxJoint, PxBase);
 DeriveClass!(PxDistanceJoint: PxJoint, PxBase);
 DeriveClass!(PxFixedJoint: PxJoint, PxBase);
+DeriveClass!(PxGearJoint: PxJoint, PxBase);
 DeriveClass!(PxPrismaticJoint: PxJoint, PxBase);
+DeriveClass!(PxRackAndPinionJoint: PxJoint, PxBase);
 DeriveClass!(PxRevoluteJoint: PxJoint, PxBase);
 DeriveClass!(PxSphericalJoint: PxJoint, PxBase);
 DeriveClass!(PxMaterial: PxRefCounted, PxBase);
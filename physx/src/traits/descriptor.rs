@@ -2,6 +2,7 @@ use crate::{
     articulation_link::ArticulationLink,
     articulation_reduced_coordinate::ArticulationReducedCoordinate,
     constraint::Constraint,
+    contact_modify_callback::{ContactModifyCallback, ContactModifyCallbackRaw, NoContactModify},
     geometry::Geometry,
     material::Material,
     math::{PxBounds3, PxTransform, PxVec3},
@@ -37,11 +38,12 @@ pub struct SceneDescriptor<
     S: RigidStatic,
     D: RigidDynamic,
     C: ArticulationReducedCoordinate,
-    OC: CollisionCallback,
+    OC: CollisionCallback<L, S, D>,
     OT: TriggerCallback,
     OCB: ConstraintBreakCallback,
     OWS: WakeSleepCallback<L, S, D>,
     OA: AdvanceCallback<L, D>,
+    OCM: ContactModifyCallback<L, S, D> = NoContactModify,
 > {
     pub phantom_marker: PhantomData<(L, S, D, C)>,
     pub user_data: U,
@@ -50,6 +52,10 @@ pub struct SceneDescriptor<
     pub on_constraint_break: Option<OCB>,
     pub on_wake_sleep: Option<OWS>,
     pub on_advance: Option<OA>,
+    /// Called to modify contacts before they are solved.
+    pub on_contact_modify: Option<OCM>,
+    /// Also pass contacts generated by continuous collision detection to `on_contact_modify`.
+    pub modify_ccd_contacts: bool,
     pub gravity: PxVec3,
     pub kine_kine_filtering_mode: PairFilteringMode,
     pub static_kine_filtering_mode: PairFilteringMode,
@@ -80,7 +86,9 @@ pub struct SceneDescriptor<
 
     pub thread_count: u32, //pub cpu_dispatcher: *mut PxCpuDispatcher,
     pub broad_phase_callback: *mut physx_sys::PxBroadPhaseCallback,
+    /// Raw contact modify callback, ignored if `on_contact_modify` is set.
     pub contact_modify_callback: *mut physx_sys::PxContactModifyCallback,
+    /// Raw CCD contact modify callback, ignored if `on_contact_modify` is set.
     pub ccd_contact_modify_callback: *mut physx_sys::PxCCDContactModifyCallback,
 
     pub gpu_dynamics_config: physx_sys::PxgDynamicsMemoryConfig,
@@ -94,12 +102,13 @@ impl<
         S: RigidStatic,
         D: RigidDynamic,
         C: ArticulationReducedCoordinate,
-        OC: CollisionCallback,
+        OC: CollisionCallback<L, S, D>,
         OT: TriggerCallback,
         OCB: ConstraintBreakCallback,
         OWS: WakeSleepCallback<L, S, D>,
         OA: AdvanceCallback<L, D>,
-    > SceneDescriptor<U, L, S, D, C, OC, OT, OCB, OWS, OA>
+        OCM: ContactModifyCallback<L, S, D>,
+    > SceneDescriptor<U, L, S, D, C, OC, OT, OCB, OWS, OA, OCM>
 {
     pub fn new(user_data: U) -> Self {
         Self {
@@ -110,6 +119,8 @@ impl<
             on_constraint_break: None,
             on_wake_sleep: None,
             on_advance: None,
+            on_contact_modify: None,
+            modify_ccd_contacts: false,
             gravity: PxVec3::new(0.0, 0.0, 0.0),
             kine_kine_filtering_mode: PairFilteringMode::Suppress,
             static_kine_filtering_mode: PairFilteringMode::Suppress,
@@ -163,17 +174,33 @@ impl<
         S: RigidStatic,
         D: RigidDynamic,
         C: ArticulationReducedCoordinate,
-        OC: CollisionCallback,
+        OC: CollisionCallback<L, S, D>,
         OT: TriggerCallback,
         OCB: ConstraintBreakCallback,
         OWS: WakeSleepCallback<L, S, D>,
         OA: AdvanceCallback<L, D>,
-    > Descriptor<P> for SceneDescriptor<U, L, S, D, C, OC, OT, OCB, OWS, OA>
+        OCM: ContactModifyCallback<L, S, D>,
+    > Descriptor<P> for SceneDescriptor<U, L, S, D, C, OC, OT, OCB, OWS, OA, OCM>
 {
     #[allow(clippy::type_complexity)]
-    type Target = Option<Owner<PxScene<U, L, S, D, C, OC, OT, OCB, OWS, OA>>>;
+    type Target = Option<Owner<PxScene<U, L, S, D, C, OC, OT, OCB, OWS, OA, OCM>>>;
 
     fn create(self, creator: &mut P) -> Self::Target {
+        let (contact_modify_callback, ccd_contact_modify_callback) = match self.on_contact_modify {
+            Some(on_contact_modify) => {
+                let callback = on_contact_modify.into_raw_callback();
+                let ccd_callback = if self.modify_ccd_contacts {
+                    unsafe { physx_sys::get_ccd_contact_modify_callback(callback) }
+                } else {
+                    null_mut()
+                };
+                (callback, ccd_callback)
+            }
+            None => (
+                self.contact_modify_callback,
+                self.ccd_contact_modify_callback,
+            ),
+        };
         let mut desc = unsafe {
             physx_sys::PxSceneDesc {
                 gravity: self.gravity.into(),
@@ -222,8 +249,8 @@ impl<
                     physx_sys::PxDefaultCpuDispatcherWaitForWorkMode::WaitForWork,
                     0,
                 ) as *mut physx_sys::PxCpuDispatcher,
-                contactModifyCallback: self.contact_modify_callback,
-                ccdContactModifyCallback: self.ccd_contact_modify_callback,
+                contactModifyCallback: contact_modify_callback,
+                ccdContactModifyCallback: ccd_contact_modify_callback,
                 broadPhaseCallback: self.broad_phase_callback,
                 gpuDynamicsConfig: self.gpu_dynamics_config,
                 gpuMaxNumPartitions: self.gpu_max_num_partitions,
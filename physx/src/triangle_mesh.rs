@@ -1,6 +1,40 @@
-use crate::{owner::Owner, traits::Class};
+use crate::{
+    geometry::MassInformation,
+    math::{PxBounds3, PxVec3},
+    owner::Owner,
+    traits::Class,
+};
 
-use physx_sys::PxTriangleMesh_release_mut;
+use std::{
+    ops::{Deref, DerefMut},
+    slice,
+};
+
+#[rustfmt::skip]
+use physx_sys::{
+    PxMat33_new,
+    PxRefCounted,
+    PxRefCounted_getReferenceCount,
+    PxTriangleMesh_canRefitBVH,
+    PxTriangleMesh_getLocalBounds,
+    PxTriangleMesh_getMassInformation,
+    PxTriangleMesh_getNbTriangles,
+    PxTriangleMesh_getNbVertices,
+    PxTriangleMesh_getTriangleMaterialIndex,
+    PxTriangleMesh_getTriangleMeshFlags,
+    PxTriangleMesh_getTriangles,
+    PxTriangleMesh_getTrianglesRemap,
+    PxTriangleMesh_getVertices,
+    PxTriangleMesh_getVerticesForModification_mut,
+    PxTriangleMesh_refitBVH_mut,
+    PxTriangleMesh_release_mut,
+};
+
+#[rustfmt::skip]
+pub use physx_sys::{
+    PxTriangleMeshFlag as TriangleMeshFlag,
+    PxTriangleMeshFlags as TriangleMeshFlags,
+};
 
 #[repr(transparent)]
 pub struct TriangleMesh {
@@ -17,6 +51,145 @@ impl TriangleMesh {
     pub unsafe fn from_raw(ptr: *mut physx_sys::PxTriangleMesh) -> Option<Owner<TriangleMesh>> {
         unsafe { Owner::from_raw(ptr as *mut Self) }
     }
+
+    /// Get the vertices of the mesh.  Cooking may reorder and weld vertices, so these
+    /// do not necessarily match the vertices the mesh was cooked from.
+    pub fn get_vertices(&self) -> &[PxVec3] {
+        unsafe {
+            slice::from_raw_parts(
+                PxTriangleMesh_getVertices(self.as_ptr()) as *const PxVec3,
+                PxTriangleMesh_getNbVertices(self.as_ptr()) as usize,
+            )
+        }
+    }
+
+    /// Get the number of triangles in the mesh.
+    pub fn get_nb_triangles(&self) -> u32 {
+        unsafe { PxTriangleMesh_getNbTriangles(self.as_ptr()) }
+    }
+
+    /// Get the vertex indices of the triangles, three per triangle.  Whether they are
+    /// 16 or 32 bit depends on [`TriangleMeshFlag::E16BitIndices`].
+    pub fn get_triangles(&self) -> TriangleMeshIndices<'_> {
+        let len = self.get_nb_triangles() as usize;
+        unsafe {
+            let triangles = PxTriangleMesh_getTriangles(self.as_ptr());
+            if self
+                .get_triangle_mesh_flags()
+                .contains(TriangleMeshFlags::E16BitIndices)
+            {
+                TriangleMeshIndices::U16(slice::from_raw_parts(triangles as *const [u16; 3], len))
+            } else {
+                TriangleMeshIndices::U32(slice::from_raw_parts(triangles as *const [u32; 3], len))
+            }
+        }
+    }
+
+    /// Get the mapping from each triangle to the index of the triangle it was cooked
+    /// from, or None if the remap table was not kept when cooking.
+    pub fn get_triangles_remap(&self) -> Option<&[u32]> {
+        unsafe {
+            let remap = PxTriangleMesh_getTrianglesRemap(self.as_ptr());
+            if remap.is_null() {
+                None
+            } else {
+                Some(slice::from_raw_parts(
+                    remap,
+                    self.get_nb_triangles() as usize,
+                ))
+            }
+        }
+    }
+
+    /// Get the flags the mesh was cooked with.
+    pub fn get_triangle_mesh_flags(&self) -> TriangleMeshFlags {
+        unsafe { PxTriangleMesh_getTriangleMeshFlags(self.as_ptr()) }
+    }
+
+    /// Get the material index of a triangle, or None if the mesh has no per-triangle materials
+    /// or the index is out of bounds.
+    pub fn get_triangle_material_index(&self, triangle_index: u32) -> Option<u16> {
+        if triangle_index >= self.get_nb_triangles() {
+            return None;
+        }
+        match unsafe { PxTriangleMesh_getTriangleMaterialIndex(self.as_ptr(), triangle_index) } {
+            u16::MAX => None,
+            index => Some(index),
+        }
+    }
+
+    /// Get the bounds of the mesh in mesh local space.
+    pub fn get_local_bounds(&self) -> PxBounds3 {
+        unsafe { PxTriangleMesh_getLocalBounds(self.as_ptr()).into() }
+    }
+
+    /// Get the mass properties of the mesh, treating it as a closed volume.
+    pub fn get_mass_information(&self) -> MassInformation {
+        let mut mass = 0.0;
+        let mut local_inertia = unsafe { PxMat33_new() };
+        let mut local_center_of_mass = PxVec3::default();
+        unsafe {
+            PxTriangleMesh_getMassInformation(
+                self.as_ptr(),
+                &mut mass,
+                &mut local_inertia,
+                local_center_of_mass.as_mut_ptr(),
+            )
+        }
+        MassInformation {
+            mass,
+            local_inertia,
+            local_center_of_mass,
+        }
+    }
+
+    /// Get the number of references to the mesh, one for the owner and one for each
+    /// shape using it.
+    pub fn get_reference_count(&self) -> u32 {
+        unsafe {
+            PxRefCounted_getReferenceCount(
+                Class::<physx_sys::PxTriangleMesh>::as_ptr(self) as *const PxRefCounted
+            )
+        }
+    }
+
+    /// Returns true if the BVH of the mesh can be refitted after moving its vertices,
+    /// which is not the case for meshes cooked with a quantized BVH34 midphase.
+    pub fn can_refit_bvh(&self) -> bool {
+        unsafe { PxTriangleMesh_canRefitBVH(self.as_ptr()) }
+    }
+
+    /// Get the vertices for modification, or None if the BVH can not be refitted or the
+    /// mesh is used by a shape.  The BVH is refitted when the returned guard is dropped or
+    /// finished.
+    ///
+    /// The vertices are the cooked vertices, cook with
+    /// [`crate::cooking::MeshPreprocessingFlag::DisableCleanMesh`] to keep them in the same
+    /// order as the input.  Moving vertices far degrades query
+    /// performance, since the BVH is refitted and not rebuilt.
+    ///
+    /// A scene may read the vertices of the shapes it simulates from any of its worker
+    /// threads, so the mesh can only be modified while the owner holds the only reference,
+    /// see [`Self::get_reference_count`].  Release the shapes using it first and create them
+    /// again afterwards.  The active edges of the mesh are lost, so contacts with dynamic
+    /// bodies can be rougher than before.
+    pub fn vertices_mut(&mut self) -> Option<TriangleMeshVerticesMut<'_>> {
+        if !self.can_refit_bvh() || self.get_reference_count() > 1 {
+            return None;
+        }
+        let len = unsafe { PxTriangleMesh_getNbVertices(self.as_ptr()) } as usize;
+        let vertices = unsafe { PxTriangleMesh_getVerticesForModification_mut(self.as_mut_ptr()) };
+        Some(TriangleMeshVerticesMut {
+            mesh: Some(self),
+            vertices: vertices as *mut PxVec3,
+            len,
+        })
+    }
+
+    /// Refit the BVH to the vertices and return the new bounds of the mesh.
+    fn refit_bvh(&mut self) -> PxBounds3 {
+        unsafe { PxTriangleMesh_refitBVH_mut(self.as_mut_ptr()).into() }
+    }
 }
 
 unsafe impl Send for TriangleMesh {}
@@ -27,3 +200,81 @@ impl Drop for TriangleMesh {
         unsafe { PxTriangleMesh_release_mut(self.as_mut_ptr()) }
     }
 }
+
+/// The vertex indices of the triangles of a [`TriangleMesh`].
+#[derive(Copy, Clone)]
+pub enum TriangleMeshIndices<'a> {
+    U16(&'a [[u16; 3]]),
+    U32(&'a [[u32; 3]]),
+}
+
+impl<'a> TriangleMeshIndices<'a> {
+    /// Get the number of triangles.
+    pub fn len(&self) -> usize {
+        match self {
+            Self::U16(triangles) => triangles.len(),
+            Self::U32(triangles) => triangles.len(),
+        }
+    }
+
+    /// Returns true if there are no triangles.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Get the vertex indices of a triangle.
+    pub fn get(&self, triangle_index: usize) -> Option<[u32; 3]> {
+        match self {
+            Self::U16(triangles) => triangles
+                .get(triangle_index)
+                .map(|&[a, b, c]| [a as u32, b as u32, c as u32]),
+            Self::U32(triangles) => triangles.get(triangle_index).copied(),
+        }
+    }
+
+    /// Iterate over the vertex indices of the triangles.
+    pub fn iter(&self) -> impl Iterator<Item = [u32; 3]> + 'a {
+        let triangles = *self;
+        (0..triangles.len()).filter_map(move |index| triangles.get(index))
+    }
+}
+
+/// The vertices of a [`TriangleMesh`] borrowed for modification, see
+/// [`TriangleMesh::vertices_mut`].  Refits the BVH of the mesh when dropped.
+pub struct TriangleMeshVerticesMut<'a> {
+    mesh: Option<&'a mut TriangleMesh>,
+    vertices: *mut PxVec3,
+    len: usize,
+}
+
+impl TriangleMeshVerticesMut<'_> {
+    /// Refit the BVH of the mesh and return its new bounds.
+    pub fn finish(mut self) -> PxBounds3 {
+        self.mesh
+            .take()
+            .map(TriangleMesh::refit_bvh)
+            .unwrap_or_default()
+    }
+}
+
+impl Deref for TriangleMeshVerticesMut<'_> {
+    type Target = [PxVec3];
+
+    fn deref(&self) -> &[PxVec3] {
+        unsafe { slice::from_raw_parts(self.vertices, self.len) }
+    }
+}
+
+impl DerefMut for TriangleMeshVerticesMut<'_> {
+    fn deref_mut(&mut self) -> &mut [PxVec3] {
+        unsafe { slice::from_raw_parts_mut(self.vertices, self.len) }
+    }
+}
+
+impl Drop for TriangleMeshVerticesMut<'_> {
+    fn drop(&mut self) {
+        if let Some(mesh) = self.mesh.take() {
+            mesh.refit_bvh();
+        }
+    }
+}